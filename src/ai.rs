@@ -0,0 +1,194 @@
+//! Provides computer players for chain reaction.
+//!
+//! # Examples
+//!
+//! ```
+//! use chain_reaction::ai;
+//! use chain_reaction::board::Board;
+//!
+//! let board = Board::new(4, 4, 2);
+//! let mut player = ai::from_name("greedy", 0).unwrap();
+//! let (row, col) = player.choose_move(&board).unwrap();
+//! assert!(board.valid_moves().contains(&(row, col)));
+//! ```
+
+use crate::board::{Board, BoardState, CellState};
+use crate::rng::Rng;
+
+/// Names of available computer players.
+pub const NAMES: [&str; 3] = ["random", "greedy", "minimax"];
+
+const WIN_SCORE: i32 = i32::MAX / 2;
+
+/// Computer player.
+pub trait Player {
+    /// Provides name of player.
+    fn name(&self) -> &str;
+
+    /// Choose move for current player of board.
+    ///
+    /// Returns `None` if board is not waiting for a move.
+    fn choose_move(&mut self, board: &Board) -> Option<(usize, usize)>;
+}
+
+/// Create computer player by name.
+///
+/// # Arguments
+///
+/// * `name` - One of [`NAMES`].
+/// * `seed` - Seed for breaking ties between equally good moves.
+///
+/// [`NAMES`]: self::NAMES
+pub fn from_name(name: &str, seed: u64) -> Option<Box<dyn Player>> {
+    let rng = Rng::new(seed);
+    match name {
+        "random" => Some(Box::new(Random { rng })),
+        "greedy" => Some(Box::new(Search {
+            name: "greedy",
+            depth: 1,
+            rng,
+        })),
+        "minimax" => Some(Box::new(Search {
+            name: "minimax",
+            depth: 2,
+            rng,
+        })),
+        _ => None,
+    }
+}
+
+/// Player making random valid moves.
+struct Random {
+    rng: Rng,
+}
+
+impl Player for Random {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn choose_move(&mut self, board: &Board) -> Option<(usize, usize)> {
+        let moves = board.valid_moves();
+        if moves.is_empty() {
+            None
+        } else {
+            Some(moves[self.rng.below(moves.len())])
+        }
+    }
+}
+
+/// Player searching moves assuming all other players play against it.
+struct Search {
+    name: &'static str,
+    depth: usize,
+    rng: Rng,
+}
+
+impl Player for Search {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn choose_move(&mut self, board: &Board) -> Option<(usize, usize)> {
+        let player = board.current_player_id();
+        let mut best = vec![];
+        let mut best_score = i32::MIN;
+        for (row, col) in board.valid_moves() {
+            let score = search(&play(board, row, col), player, self.depth - 1);
+            if score > best_score {
+                best_score = score;
+                best.clear();
+            }
+            if score == best_score {
+                best.push((row, col));
+            }
+        }
+        if best.is_empty() {
+            None
+        } else {
+            Some(best[self.rng.below(best.len())])
+        }
+    }
+}
+
+fn play(board: &Board, row: usize, col: usize) -> Board {
+    let mut board = board.clone();
+    let player = board.current_player_id();
+    if board.player_move(player, row, col).is_ok() {
+        board.settle();
+    }
+    board
+}
+
+fn search(board: &Board, player: u8, depth: usize) -> i32 {
+    if depth == 0 || !matches!(board.state(), BoardState::Wait) {
+        return score(board, player);
+    }
+    let scores = board
+        .valid_moves()
+        .into_iter()
+        .map(|(row, col)| search(&play(board, row, col), player, depth - 1));
+    if board.current_player_id() == player {
+        scores.max()
+    } else {
+        scores.min()
+    }
+    .unwrap_or_else(|| score(board, player))
+}
+
+/// Atoms owned by player minus atoms owned by others.
+fn score(board: &Board, player: u8) -> i32 {
+    match board.state() {
+        BoardState::GameOver(winner) if *winner == player => return WIN_SCORE,
+        BoardState::GameOver(_) => return -WIN_SCORE,
+        _ => {}
+    }
+    board
+        .cells()
+        .into_iter()
+        .flatten()
+        .map(|cell| match cell {
+            CellState::NonEmpty(owner, atoms) if owner == player => atoms as i32,
+            CellState::NonEmpty(_, atoms) => -(atoms as i32),
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_player_makes_valid_move() {
+        let board = Board::new(3, 3, 2);
+        for name in NAMES {
+            let mut player = from_name(name, 1).unwrap();
+            assert_eq!(player.name(), name);
+            let (row, col) = player.choose_move(&board).unwrap();
+            assert!(board.valid_moves().contains(&(row, col)));
+        }
+    }
+
+    #[test]
+    fn search_finds_winning_move() {
+        let mut cells = vec![vec![CellState::Empty; 3]; 3];
+        cells[0][0] = CellState::NonEmpty(0, 1);
+        cells[1][0] = CellState::NonEmpty(1, 1);
+        let board = Board::from_cells(cells, 2, 0).unwrap();
+        for name in ["greedy", "minimax"] {
+            let mut player = from_name(name, 1).unwrap();
+            assert_eq!(player.choose_move(&board), Some((0, 0)));
+        }
+    }
+
+    #[test]
+    fn no_move_when_game_over() {
+        let mut board = Board::new(3, 3, 2);
+        for (player, row, col) in [(0, 0, 0), (1, 1, 0), (0, 0, 0)] {
+            board.player_move(player, row, col).unwrap();
+            board.settle();
+        }
+        assert_eq!(from_name("random", 1).unwrap().choose_move(&board), None);
+    }
+}
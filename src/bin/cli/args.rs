@@ -0,0 +1,547 @@
+use chain_reaction::ai;
+use chain_reaction::board::{Rules, Teams, MAX_PLAYERS, MAX_SIZE, MIN_SIZE};
+use chain_reaction::tournament::Tournament;
+
+use crate::playback::{Playback, WAVE_DELAY};
+use crate::render::color_supported;
+use std::io::IsTerminal;
+use std::time::Duration;
+
+pub const USAGE: &str = "usage: cli [options]
+       cli puzzle [--no-color] <file>
+       cli replay [--no-color] <file>
+       cli tournament [tournament options]
+       cli engine [--ai <name>] [--seed <seed>]
+       cli connect [--no-color] <host>:<port> (--name <name> | --watch) [--room <code>]
+
+options:
+  --rows <rows>              rows in board, 3 to 20 (default 10)
+  --cols <cols>              columns in board, 3 to 20 (default 10)
+  --players <players>        number of players, 2 to 8 (default 2)
+  --rules <rules>            classic or wrap (default classic)
+  --teams <size>v<size>...   teams of equal size, e.g. 2v2 or 3v3, seats
+                             alternate between teams
+  --team-capture             explosions capture cells of teammates too
+  --ai <seat>=<name>         player for seat: human, random, greedy or minimax
+  --engine <seat>=<command>  external engine program for seat, see `cli engine`
+  --engine-time <ms>         time engines get for each move (default 1000)
+  --watch                    computer players for all seats, greedy unless set by --ai
+  --delay <ms>               pause before computer moves (default 0, 500 with --watch)
+  --seed <seed>              seed for computer players (default random)
+  --playback <mode>          explosion waves: instant, step or timed
+                             (default timed on terminals, instant otherwise)
+  --wave-delay <ms>          pause between waves in timed playback (default 300)
+  --load <file>              continue game from saved or recorded file
+  --save <file>              save game to file when quitting
+  --record <file>            write game record to file after every move
+  --no-color                 plain board even on colour terminals
+  --help                     show this help
+
+tournament options:
+  --ai <name>,<name>...      computer players (default all)
+  --boards <rows>x<cols>,... board sizes (default 10x10)
+  --players <players>        players in each game (default 2)
+  --rounds <games>           games for each seating (default 10)
+  --seed <seed>              seed for computer players (default 0)
+  --threads <threads>        games played in parallel (default cpu count)
+
+engine answers engine protocol on stdin and stdout with a computer player
+(default minimax), so it can be used as `--engine <seat>=cli engine`
+
+connect plays game hosted by `server`, joining lobby of its default room
+or room with given code, with --watch it follows games without playing";
+
+const HUMAN: &str = "human";
+/// Computer player of seats left free with `--watch`.
+pub const WATCH_AI: &str = "greedy";
+const WATCH_DELAY: Duration = Duration::from_millis(500);
+const ENGINE_AI: &str = "minimax";
+const ENGINE_TIME: Duration = Duration::from_millis(1000);
+
+pub struct Options {
+    pub rows: usize,
+    pub cols: usize,
+    pub players: u8,
+    pub rules: Rules,
+    pub teams: Option<Teams>,
+    pub ais: Vec<(u8, String)>,
+    pub engines: Vec<(u8, String)>,
+    /// Seats without computer player or engine are played by [`WATCH_AI`].
+    pub watch: bool,
+    pub engine_time: Duration,
+    pub delay: Duration,
+    pub seed: u64,
+    pub playback: Playback,
+    pub load: Option<String>,
+    pub save: Option<String>,
+    pub record: Option<String>,
+    pub color: bool,
+}
+
+pub enum Command {
+    Play(Options),
+    Puzzle {
+        path: String,
+        color: bool,
+    },
+    Replay {
+        path: String,
+        color: bool,
+    },
+    Tournament(Tournament),
+    Engine {
+        ai: String,
+        seed: u64,
+    },
+    Connect {
+        address: String,
+        /// Name of player, `None` for spectators.
+        name: Option<String>,
+        room: Option<String>,
+        color: bool,
+    },
+    Help,
+}
+
+fn random_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |time| time.as_nanos() as u64)
+}
+
+/// Provides number of teams and players in each team, from e.g. `2v2`.
+fn parse_teams(teams: &str) -> Option<(u8, u8)> {
+    let sizes: Vec<u8> = teams
+        .split('v')
+        .map(|size| size.parse().ok())
+        .collect::<Option<_>>()?;
+    let size = *sizes.first()?;
+    (sizes.len() >= 2 && size > 0 && sizes.iter().all(|s| *s == size))
+        .then_some((sizes.len() as u8, size))
+}
+
+fn parse_board(board: &str) -> Option<(usize, usize)> {
+    let (rows, cols) = board.split_once('x')?;
+    Some((rows.parse().ok()?, cols.parse().ok()?))
+}
+
+/// Provides pairs of flag and its value.
+fn flags<'a>(args: &'a [&'a str]) -> impl Iterator<Item = Result<(&'a str, &'a str), String>> {
+    let mut args = args.iter();
+    std::iter::from_fn(move || {
+        let flag = *args.next()?;
+        if flag == "--help" || flag == "--watch" || flag == "--team-capture" {
+            return Some(Ok((flag, "")));
+        }
+        if !flag.starts_with("--") {
+            return Some(Err(format!("unexpected argument {}", flag)));
+        }
+        Some(
+            args.next()
+                .map(|value| (flag, *value))
+                .ok_or_else(|| format!("missing value for {}", flag)),
+        )
+    })
+}
+
+fn invalid(flag: &str, value: &str) -> String {
+    format!("invalid value for {}: {}", flag, value)
+}
+
+fn parse_play(args: &[&str], color: bool) -> Result<Command, String> {
+    let mut rows = None;
+    let mut cols = None;
+    let mut players = None;
+    let mut rules = None;
+    let mut teams = None;
+    let mut team_capture = false;
+    let mut delay = None;
+    let mut watch = false;
+    let mut playback = None;
+    let mut wave_delay = WAVE_DELAY;
+    let mut options = Options {
+        rows: 10,
+        cols: 10,
+        players: 2,
+        rules: Rules::Classic,
+        teams: None,
+        ais: vec![],
+        engines: vec![],
+        watch: false,
+        engine_time: ENGINE_TIME,
+        delay: Duration::ZERO,
+        seed: random_seed(),
+        playback: Playback::Instant,
+        load: None,
+        save: None,
+        record: None,
+        color,
+    };
+    for flag in flags(args) {
+        let (flag, value) = flag?;
+        match flag {
+            "--help" => return Ok(Command::Help),
+            "--rows" => rows = Some(value.parse().map_err(|_| invalid(flag, value))?),
+            "--cols" => cols = Some(value.parse().map_err(|_| invalid(flag, value))?),
+            "--players" => players = Some(value.parse().map_err(|_| invalid(flag, value))?),
+            "--rules" => rules = Some(Rules::from_name(value).ok_or_else(|| invalid(flag, value))?),
+            "--teams" => teams = Some(parse_teams(value).ok_or_else(|| invalid(flag, value))?),
+            "--team-capture" => team_capture = true,
+            "--ai" => {
+                let (seat, name) = value.split_once('=').ok_or_else(|| invalid(flag, value))?;
+                let seat = seat.parse().map_err(|_| invalid(flag, value))?;
+                if name != HUMAN && !ai::NAMES.contains(&name) {
+                    return Err(format!("unknown computer player {}", name));
+                }
+                if options
+                    .ais
+                    .iter()
+                    .chain(&options.engines)
+                    .any(|(s, _)| *s == seat)
+                {
+                    return Err(format!("seat {} assigned more than once", seat));
+                }
+                options.ais.push((seat, name.to_string()));
+            }
+            "--engine" => {
+                let (seat, command) = value.split_once('=').ok_or_else(|| invalid(flag, value))?;
+                let seat = seat.parse().map_err(|_| invalid(flag, value))?;
+                if command.trim().is_empty() {
+                    return Err(invalid(flag, value));
+                }
+                if options
+                    .ais
+                    .iter()
+                    .chain(&options.engines)
+                    .any(|(s, _)| *s == seat)
+                {
+                    return Err(format!("seat {} assigned more than once", seat));
+                }
+                options.engines.push((seat, command.to_string()));
+            }
+            "--engine-time" => {
+                let ms = value.parse().map_err(|_| invalid(flag, value))?;
+                options.engine_time = Duration::from_millis(ms);
+            }
+            "--watch" => watch = true,
+            "--delay" => {
+                let ms = value.parse().map_err(|_| invalid(flag, value))?;
+                delay = Some(Duration::from_millis(ms));
+            }
+            "--seed" => options.seed = value.parse().map_err(|_| invalid(flag, value))?,
+            "--playback" => playback = Some(value),
+            "--wave-delay" => {
+                let ms = value.parse().map_err(|_| invalid(flag, value))?;
+                wave_delay = Duration::from_millis(ms);
+            }
+            "--load" => options.load = Some(value.to_string()),
+            "--save" => options.save = Some(value.to_string()),
+            "--record" => options.record = Some(value.to_string()),
+            _ => return Err(format!("unknown option {}", flag)),
+        }
+    }
+    if options.load.is_some()
+        && ((rows, cols, players, rules) != (None, None, None, None) || teams.is_some())
+    {
+        return Err("--load takes board from file, it cannot be combined with \
+                    --rows, --cols, --players, --rules or --teams"
+            .to_string());
+    }
+    if let Some((count, size)) = teams {
+        if players.is_some_and(|players| players != count * size) {
+            return Err(format!("--teams {} needs {} players", count, count * size));
+        }
+        if !options.engines.is_empty() {
+            return Err("--engine cannot be combined with --teams".to_string());
+        }
+        players = Some(count * size);
+        options.teams = Some(Teams {
+            count,
+            capture: team_capture,
+        });
+    } else if team_capture {
+        return Err("--team-capture needs --teams".to_string());
+    }
+    options.rows = rows.unwrap_or(options.rows);
+    options.cols = cols.unwrap_or(options.cols);
+    options.players = players.unwrap_or(options.players);
+    options.rules = rules.unwrap_or(options.rules);
+    let sizes = MIN_SIZE..=MAX_SIZE;
+    if !sizes.contains(&options.rows) || !sizes.contains(&options.cols) {
+        return Err(format!(
+            "board needs {} to {} rows and columns",
+            MIN_SIZE, MAX_SIZE
+        ));
+    }
+    if !(2..=MAX_PLAYERS).contains(&options.players) {
+        return Err(format!("players should be between 2 and {}", MAX_PLAYERS));
+    }
+    // Players of loaded games are known once the file is read.
+    let mut seats = options.ais.iter().chain(&options.engines);
+    if let Some((seat, _)) = seats.find(|(s, _)| options.load.is_none() && *s >= options.players) {
+        return Err(format!("seat {} does not exist", seat));
+    }
+    if watch && options.ais.iter().any(|(_, name)| name == HUMAN) {
+        return Err("--watch cannot have human players".to_string());
+    }
+    options.watch = watch;
+    options.ais.retain(|(_, name)| name != HUMAN);
+    options.playback = match playback {
+        Some(name) => {
+            Playback::from_name(name, wave_delay).ok_or_else(|| invalid("--playback", name))?
+        }
+        None if std::io::stdout().is_terminal() => Playback::Timed(wave_delay),
+        None => Playback::Instant,
+    };
+    options.delay = delay.unwrap_or(if watch { WATCH_DELAY } else { Duration::ZERO });
+    Ok(Command::Play(options))
+}
+
+fn parse_tournament(args: &[&str]) -> Result<Command, String> {
+    let mut tournament = Tournament {
+        ais: ai::NAMES.iter().map(|name| name.to_string()).collect(),
+        boards: vec![(10, 10)],
+        players: 2,
+        rounds: 10,
+        seed: 0,
+        threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    for flag in flags(args) {
+        let (flag, value) = flag?;
+        match flag {
+            "--help" => return Ok(Command::Help),
+            "--ai" => tournament.ais = value.split(',').map(str::to_string).collect(),
+            "--boards" => {
+                tournament.boards = value
+                    .split(',')
+                    .map(parse_board)
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| invalid(flag, value))?
+            }
+            "--players" => tournament.players = value.parse().map_err(|_| invalid(flag, value))?,
+            "--rounds" => tournament.rounds = value.parse().map_err(|_| invalid(flag, value))?,
+            "--seed" => tournament.seed = value.parse().map_err(|_| invalid(flag, value))?,
+            "--threads" => tournament.threads = value.parse().map_err(|_| invalid(flag, value))?,
+            _ => return Err(format!("unknown option {}", flag)),
+        }
+    }
+    Ok(Command::Tournament(tournament))
+}
+
+fn parse_engine(args: &[&str]) -> Result<Command, String> {
+    let mut ai = ENGINE_AI.to_string();
+    let mut seed = random_seed();
+    for flag in flags(args) {
+        let (flag, value) = flag?;
+        match flag {
+            "--help" => return Ok(Command::Help),
+            "--ai" if ai::NAMES.contains(&value) => ai = value.to_string(),
+            "--ai" => return Err(format!("unknown computer player {}", value)),
+            "--seed" => seed = value.parse().map_err(|_| invalid(flag, value))?,
+            _ => return Err(format!("unknown option {}", flag)),
+        }
+    }
+    Ok(Command::Engine { ai, seed })
+}
+
+fn parse_connect(address: &str, args: &[&str], color: bool) -> Result<Command, String> {
+    let mut name = None;
+    let mut room = None;
+    let mut watch = false;
+    for flag in flags(args) {
+        let (flag, value) = flag?;
+        match flag {
+            "--help" => return Ok(Command::Help),
+            "--watch" => watch = true,
+            "--name" if value.trim().is_empty() => return Err(invalid(flag, value)),
+            "--name" => name = Some(value.trim().to_string()),
+            "--room" => room = Some(value.trim().to_uppercase()),
+            _ => return Err(format!("unknown option {}", flag)),
+        }
+    }
+    if watch == name.is_some() {
+        return Err("connect needs either --name or --watch".to_string());
+    }
+    Ok(Command::Connect {
+        address: address.to_string(),
+        name,
+        room,
+        color,
+    })
+}
+
+/// Parse command line arguments without program name.
+pub fn parse(args: &[&str]) -> Result<Command, String> {
+    let color = color_supported() && !args.contains(&"--no-color");
+    let args: Vec<&str> = args
+        .iter()
+        .copied()
+        .filter(|arg| *arg != "--no-color")
+        .collect();
+    match args[..] {
+        ["puzzle", path] => Ok(Command::Puzzle {
+            path: path.to_string(),
+            color,
+        }),
+        ["puzzle", ..] => Err("puzzle needs exactly one file".to_string()),
+        ["replay", path] => Ok(Command::Replay {
+            path: path.to_string(),
+            color,
+        }),
+        ["replay", ..] => Err("replay needs exactly one file".to_string()),
+        ["tournament", ref args @ ..] => parse_tournament(args),
+        ["engine", ref args @ ..] => parse_engine(args),
+        ["connect", address, ref args @ ..] if !address.starts_with("--") => {
+            parse_connect(address, args, color)
+        }
+        ["connect", ..] => Err("connect needs server address, e.g. `localhost:7878`".to_string()),
+        ref args => parse_play(args, color),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(args: &[&str]) -> Result<Options, String> {
+        match parse(args)? {
+            Command::Play(options) => Ok(options),
+            _ => Err("not play".to_string()),
+        }
+    }
+
+    #[test]
+    fn play_options() {
+        let options = play(&[
+            "--rows",
+            "5",
+            "--cols",
+            "6",
+            "--players",
+            "3",
+            "--rules",
+            "wrap",
+            "--ai",
+            "2=greedy",
+            "--seed",
+            "9",
+        ])
+        .unwrap();
+        assert_eq!((options.rows, options.cols, options.players), (5, 6, 3));
+        assert_eq!(options.rules, Rules::Wrap);
+        assert_eq!(options.ais, vec![(2, "greedy".to_string())]);
+        assert_eq!(options.seed, 9);
+        assert_eq!(options.playback, Playback::Instant);
+        let options = play(&["--load", "a.txt", "--save", "b.txt"]).unwrap();
+        assert_eq!(options.load.as_deref(), Some("a.txt"));
+        assert_eq!(options.save.as_deref(), Some("b.txt"));
+        let options = play(&["--playback", "timed", "--wave-delay", "50"]).unwrap();
+        assert_eq!(options.playback, Playback::Timed(Duration::from_millis(50)));
+        assert!(!play(&["--no-color"]).unwrap().color);
+        let options = play(&["--teams", "3v3", "--team-capture"]).unwrap();
+        assert_eq!(options.players, 6);
+        assert_eq!(
+            options.teams,
+            Some(Teams {
+                count: 2,
+                capture: true
+            })
+        );
+    }
+
+    #[test]
+    fn watch_and_loaded_seats() {
+        let options = play(&["--players", "3", "--ai", "1=random", "--watch"]).unwrap();
+        assert!(options.watch);
+        assert_eq!(options.ais, vec![(1, "random".to_string())]);
+        assert_eq!(options.delay, WATCH_DELAY);
+        let options = play(&["--load", "game.txt", "--ai", "2=random"]).unwrap();
+        assert_eq!(options.ais, vec![(2, "random".to_string())]);
+        let options = play(&["--ai", "0=human", "--ai", "1=random"]).unwrap();
+        assert_eq!(options.ais, vec![(1, "random".to_string())]);
+        assert_eq!(options.delay, Duration::ZERO);
+    }
+
+    #[test]
+    fn invalid_play_options() {
+        for args in [
+            &["--rows", "2"][..],
+            &["--rows", "21"],
+            &["--rows", "1000000", "--cols", "1000000"],
+            &["--rows"],
+            &["--rows", "x"],
+            &["--players", "9"],
+            &["--rules", "hex"],
+            &["--ai", "2=greedy"],
+            &["--ai", "0=perfect"],
+            &["--ai", "0=random", "--ai", "0=greedy"],
+            &["--ai", "0=random", "--engine", "0=bot"],
+            &["--engine", "2=bot"],
+            &["--engine", "1="],
+            &["--load", "game.txt", "--rows", "5"],
+            &["--watch", "--ai", "1=human"],
+            &["--teams", "2v3"],
+            &["--teams", "2"],
+            &["--teams", "2v2", "--players", "3"],
+            &["--teams", "5v5"],
+            &["--team-capture"],
+            &["--delay", "-1"],
+            &["--playback", "slow"],
+            &["--wave-delay", "soon"],
+            &["--colour"],
+            &["5"],
+        ] {
+            assert!(play(args).is_err(), "{:?}", args);
+        }
+    }
+
+    #[test]
+    fn subcommands() {
+        assert!(matches!(parse(&["--help"]), Ok(Command::Help)));
+        assert!(matches!(
+            parse(&["--no-color", "puzzle", "a.txt"]),
+            Ok(Command::Puzzle { color: false, .. })
+        ));
+        assert!(parse(&["puzzle"]).is_err());
+        assert!(matches!(
+            parse(&["replay", "game.txt"]),
+            Ok(Command::Replay { .. })
+        ));
+        match parse(&["tournament", "--boards", "3x3,4x5"]) {
+            Ok(Command::Tournament(t)) => assert_eq!(t.boards, vec![(3, 3), (4, 5)]),
+            _ => panic!("tournament expected"),
+        }
+        match parse(&["engine", "--ai", "random", "--seed", "3"]) {
+            Ok(Command::Engine { ai, seed }) => assert_eq!((ai.as_str(), seed), ("random", 3)),
+            _ => panic!("engine expected"),
+        }
+        assert!(parse(&["engine", "--ai", "perfect"]).is_err());
+        match parse(&[
+            "connect",
+            "localhost:7878",
+            "--name",
+            "alice",
+            "--room",
+            "kxrw",
+        ]) {
+            Ok(Command::Connect {
+                address,
+                name,
+                room,
+                ..
+            }) => {
+                assert_eq!(address, "localhost:7878");
+                assert_eq!(name.as_deref(), Some("alice"));
+                assert_eq!(room.as_deref(), Some("KXRW"));
+            }
+            _ => panic!("connect expected"),
+        }
+        assert!(matches!(
+            parse(&["connect", "localhost:7878", "--watch"]),
+            Ok(Command::Connect { name: None, .. })
+        ));
+        assert!(parse(&["connect", "localhost:7878"]).is_err());
+        assert!(parse(&["connect", "localhost:7878", "--watch", "--name", "bob"]).is_err());
+        assert!(parse(&["connect", "--name", "alice"]).is_err());
+    }
+}
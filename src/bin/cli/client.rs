@@ -0,0 +1,331 @@
+use chain_reaction::board::Board;
+use chain_reaction::clock::{self, TimeoutAction};
+use chain_reaction::net::{self, ClientMessage, GameState, ServerMessage};
+use chain_reaction::record::column_letters;
+use std::io::{BufRead, BufReader, IsTerminal, Write};
+use std::net::TcpStream;
+use std::sync::mpsc::{self, Sender};
+use std::time::Duration;
+
+use crate::command::{self, Command, HELP};
+use crate::playback::{self, Animation, Playback, WAVE_DELAY};
+use crate::render::{self, Renderer};
+
+const RECONNECT_TRIES: usize = 5;
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// What client waits on, server and player at the keyboard.
+enum Event {
+    Server(ServerMessage),
+    Closed,
+    Input(Option<String>),
+}
+
+/// Connects to server and joins room with name, messages are sent as events.
+fn connect(
+    address: &str,
+    join: &ClientMessage,
+    events: Sender<Event>,
+) -> std::io::Result<TcpStream> {
+    let mut stream = TcpStream::connect(address)?;
+    let lines = BufReader::new(stream.try_clone()?).lines();
+    std::thread::spawn(move || {
+        for line in lines.map_while(Result::ok) {
+            // Server only sends valid messages, anything else is from newer servers.
+            if let Ok(message) = serde_json::from_str(&line) {
+                if events.send(Event::Server(message)).is_err() {
+                    return;
+                }
+            }
+        }
+        let _ = events.send(Event::Closed);
+    });
+    send(&mut stream, join)?;
+    Ok(stream)
+}
+
+fn send(stream: &mut TcpStream, message: &ClientMessage) -> std::io::Result<()> {
+    let line = serde_json::to_string(message).expect("message is serializable");
+    writeln!(stream, "{}", line)
+}
+
+struct Client {
+    renderer: Renderer,
+    animation: Animation,
+    /// Whether client only watches games.
+    watching: bool,
+    player: Option<u8>,
+    /// Secret for taking back seat after reconnecting.
+    token: Option<String>,
+    state: Option<GameState>,
+    /// Empty board of the game, for critical masses of cells.
+    board: Option<Board>,
+}
+
+impl Client {
+    fn name(&self, player: u8) -> String {
+        let name = self
+            .state
+            .as_ref()
+            .and_then(|state| state.names.get(player as usize));
+        match name {
+            Some(name) => format!("{} ({})", name, self.renderer.player(player)),
+            None => self.renderer.player(player),
+        }
+    }
+
+    fn my_turn(&self) -> bool {
+        match (&self.state, self.player) {
+            (Some(state), Some(player)) => state.winner.is_none() && state.turn == player,
+            _ => false,
+        }
+    }
+
+    fn draw(&self, cells: &net::Cells, exploding: &[(usize, usize)]) -> String {
+        let board = self.board.as_ref().expect("board is known with state");
+        self.renderer
+            .cells(&net::cell_states(cells, exploding), |row, col| {
+                board.critical_mass(row, col)
+            })
+    }
+
+    /// Shows message, `false` once game is over for players.
+    fn receive(&mut self, message: ServerMessage) -> bool {
+        match message {
+            ServerMessage::Lobby { names, players } => {
+                println!(
+                    "waiting for players ({}/{}): {}",
+                    names.len(),
+                    players,
+                    names.join(", ")
+                );
+            }
+            ServerMessage::Welcome { player, token } => {
+                self.player = Some(player);
+                self.token = Some(token);
+                println!("game started, you are {}", self.renderer.player(player));
+            }
+            ServerMessage::State(state) => {
+                self.animation.finish();
+                let players = state.names.len() as u8;
+                self.board = Some(Board::with_rules(
+                    state.rows,
+                    state.cols,
+                    players,
+                    state.rules,
+                ));
+                self.state = Some(state);
+                let state = self.state.as_ref().expect("state was just set");
+                println!("{}", self.draw(&state.cells, &[]));
+                if state.is_over() {
+                    match state.winner {
+                        Some(winner) => println!("{} won", self.name(winner)),
+                        None => println!("game drawn"),
+                    }
+                    if state.names.len() > 2 {
+                        println!("{}", render::standings(&state.standings, |p| self.name(p)));
+                    }
+                    self.token = None;
+                    return self.watching;
+                }
+                let left = match state.time_left {
+                    Some(ref left) => format!(
+                        " ({} left)",
+                        clock::minutes(Duration::from_millis(left[state.turn as usize]))
+                    ),
+                    None => String::new(),
+                };
+                if self.my_turn() {
+                    println!("your move{}:", left);
+                } else {
+                    println!("waiting for {}{}", self.name(state.turn), left);
+                }
+            }
+            ServerMessage::Moved { player, row, col } => {
+                println!(
+                    "{} played {}{}",
+                    self.name(player),
+                    column_letters(col),
+                    row + 1
+                );
+            }
+            ServerMessage::Wave {
+                wave,
+                exploding,
+                cells,
+            } => {
+                if self.board.is_some() {
+                    let frame = self.draw(&cells, &exploding);
+                    self.animation.frame(&format!(
+                        "{}{}",
+                        frame,
+                        playback::label(wave, &exploding)
+                    ));
+                }
+            }
+            ServerMessage::Left { player } => {
+                println!("{} lost connection", self.name(player));
+            }
+            ServerMessage::Returned { player } => {
+                println!("{} is back", self.name(player));
+            }
+            ServerMessage::Resigned { player } => {
+                println!("{} resigned", self.name(player));
+            }
+            ServerMessage::DrawOffered { player } => {
+                println!("{} offers a draw, `accept` or `decline`", self.name(player));
+            }
+            ServerMessage::DrawAccepted { player } => {
+                println!("{} accepts the draw", self.name(player));
+            }
+            ServerMessage::DrawDeclined { player } => {
+                println!("{} declines the draw", self.name(player));
+            }
+            ServerMessage::Forfeited { player } => {
+                println!("{} did not return and is out", self.name(player));
+            }
+            ServerMessage::TimedOut { player, action } => {
+                let outcome = match action {
+                    TimeoutAction::Forfeit => "is out",
+                    TimeoutAction::RandomMove => "gets random move",
+                    TimeoutAction::Skip => "loses the turn",
+                };
+                println!("{} ran out of time and {}", self.name(player), outcome);
+            }
+            ServerMessage::Room { code } => println!("room {}", code),
+            ServerMessage::Error { message } => println!("server: {}", message),
+        }
+        true
+    }
+
+    /// Provides message for command typed by player, if any.
+    fn input(&self, line: &str) -> Result<Option<ClientMessage>, String> {
+        match command::parse(line)? {
+            Command::Move(row, col) if self.my_turn() => Ok(Some(ClientMessage::Move { row, col })),
+            Command::Move(..)
+            | Command::Resign
+            | Command::OfferDraw
+            | Command::AcceptDraw
+            | Command::DeclineDraw
+                if self.watching =>
+            {
+                Err("you are watching".to_string())
+            }
+            Command::Move(..) => Err("wait for your turn".to_string()),
+            Command::Resign | Command::OfferDraw | Command::AcceptDraw | Command::DeclineDraw
+                if self.player.is_none() =>
+            {
+                Err("game has not started".to_string())
+            }
+            Command::Resign => Ok(Some(ClientMessage::Resign)),
+            Command::OfferDraw => Ok(Some(ClientMessage::OfferDraw)),
+            Command::AcceptDraw => Ok(Some(ClientMessage::AcceptDraw)),
+            Command::DeclineDraw => Ok(Some(ClientMessage::DeclineDraw)),
+            Command::Help => {
+                println!("{}", HELP);
+                Ok(None)
+            }
+            _ => Err(
+                "not available in network games, only moves, resign, draws, help and quit"
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+/// Plays game hosted by server at address, watches games if no name is given.
+pub fn run(
+    address: &str,
+    name: Option<String>,
+    room: Option<String>,
+    color: bool,
+) -> Result<(), String> {
+    let (events, received) = mpsc::channel();
+    let failed = |err: std::io::Error| format!("cannot connect to {}: {}", address, err);
+    let connected = match name {
+        Some(ref name) => format!("connected to {} as {}", address, name),
+        None => format!("watching games on {}", address),
+    };
+    let watching = name.is_none();
+    let join = match name {
+        Some(name) => ClientMessage::Join { name, room },
+        None => ClientMessage::Watch { room },
+    };
+    let mut stream = connect(address, &join, events.clone()).map_err(failed)?;
+    let input = events.clone();
+    std::thread::spawn(move || {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match std::io::stdin().read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    if input.send(Event::Input(Some(line.clone()))).is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+        let _ = input.send(Event::Input(None));
+    });
+    let playback = if std::io::stdout().is_terminal() {
+        Playback::Timed(WAVE_DELAY)
+    } else {
+        Playback::Instant
+    };
+    let mut client = Client {
+        renderer: Renderer::new(color),
+        animation: Animation::new(playback),
+        watching,
+        player: None,
+        token: None,
+        state: None,
+        board: None,
+    };
+    println!("{}", connected);
+    for event in received.iter() {
+        match event {
+            Event::Server(message) => {
+                if !client.receive(message) {
+                    return Ok(());
+                }
+            }
+            Event::Input(None) => return Ok(()),
+            Event::Input(Some(line)) => {
+                if command::parse(&line) == Ok(Command::Quit) {
+                    return Ok(());
+                }
+                match client.input(&line) {
+                    Ok(Some(message)) => {
+                        // Lost connection is noticed by reader, which reconnects.
+                        let _ = send(&mut stream, &message);
+                    }
+                    Ok(None) => {}
+                    Err(err) => println!("{}", err),
+                }
+            }
+            Event::Closed => {
+                println!("lost connection to {}, reconnecting", address);
+                // Seat is taken back by token, lobby is joined afresh.
+                let join = match client.token {
+                    Some(ref token) => ClientMessage::Rejoin {
+                        token: token.clone(),
+                    },
+                    None => join.clone(),
+                };
+                let mut tries = 0;
+                stream = loop {
+                    if tries == RECONNECT_TRIES {
+                        return Err(format!("cannot reconnect to {}", address));
+                    }
+                    tries += 1;
+                    std::thread::sleep(RECONNECT_DELAY);
+                    if let Ok(stream) = connect(address, &join, events.clone()) {
+                        break stream;
+                    }
+                };
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use chain_reaction::record::parse_cell;
+
+pub const HELP: &str = "commands:
+  <row> <col>      place atom, counting from 0 (e.g. `2 3`)
+  <col><row>       place atom, column letters and row from 1 (e.g. `d3`)
+  undo             take back last move (and computer replies)
+  redo             play again move taken back
+  hint             suggest a move
+  save <file>      save game to file
+  load <file>      continue game saved in file
+  history          list moves played
+  resign           give up, leaving the game to other players
+  draw             offer other players a draw
+  accept           agree to draw offered
+  decline          turn down draw offered
+  help             show this help
+  quit             leave the game (saved if started with --save)";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Move(usize, usize),
+    Undo,
+    Redo,
+    Hint,
+    Save(String),
+    Load(String),
+    History,
+    Resign,
+    OfferDraw,
+    AcceptDraw,
+    DeclineDraw,
+    Help,
+    Quit,
+}
+
+pub fn parse(line: &str) -> Result<Command, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let path = |command: &str| match words[1..] {
+        [path] => Ok(path.to_string()),
+        _ => Err(format!(
+            "{} needs a file name, e.g. `{} game.txt`",
+            command, command
+        )),
+    };
+    match words[..] {
+        [] => Err("empty input, type `help` for commands".to_string()),
+        ["undo"] => Ok(Command::Undo),
+        ["redo"] => Ok(Command::Redo),
+        ["hint"] => Ok(Command::Hint),
+        ["save", ..] => path("save").map(Command::Save),
+        ["load", ..] => path("load").map(Command::Load),
+        ["history"] => Ok(Command::History),
+        ["resign"] => Ok(Command::Resign),
+        ["draw"] => Ok(Command::OfferDraw),
+        ["accept"] => Ok(Command::AcceptDraw),
+        ["decline"] => Ok(Command::DeclineDraw),
+        ["help"] => Ok(Command::Help),
+        ["quit"] => Ok(Command::Quit),
+        [row, col] => match (row.parse(), col.parse()) {
+            (Ok(row), Ok(col)) => Ok(Command::Move(row, col)),
+            _ => Err(format!(
+                "`{}` is not a move, row and column should be numbers",
+                line.trim()
+            )),
+        },
+        [word] => parse_cell(word).map_or_else(
+            || {
+                Err(format!(
+                    "unknown command `{}`, type `help` for commands",
+                    word
+                ))
+            },
+            |(row, col)| Ok(Command::Move(row, col)),
+        ),
+        _ => Err(format!(
+            "unknown command `{}`, type `help` for commands",
+            line.trim()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves() {
+        assert_eq!(parse("2 3"), Ok(Command::Move(2, 3)));
+        assert_eq!(parse(" 0   9 \n"), Ok(Command::Move(0, 9)));
+        assert_eq!(parse("c4"), Ok(Command::Move(3, 2)));
+        assert_eq!(parse("a1"), Ok(Command::Move(0, 0)));
+        assert_eq!(parse("ab12"), Ok(Command::Move(11, 27)));
+        assert!(parse("a0").is_err());
+        assert!(parse("2 x").is_err());
+        assert!(parse("C4").is_err());
+    }
+
+    #[test]
+    fn commands() {
+        assert_eq!(parse("undo"), Ok(Command::Undo));
+        assert_eq!(parse("draw"), Ok(Command::OfferDraw));
+        assert_eq!(parse("decline"), Ok(Command::DeclineDraw));
+        assert_eq!(parse("save a.txt"), Ok(Command::Save("a.txt".to_string())));
+        assert_eq!(parse("load b.txt"), Ok(Command::Load("b.txt".to_string())));
+        assert!(parse("save").is_err());
+        assert!(parse("").is_err());
+        assert!(parse("jump").is_err());
+    }
+}
@@ -0,0 +1,437 @@
+use chain_reaction::ai::{self, Player};
+use chain_reaction::board::{Board, BoardState, MoveError};
+use chain_reaction::engine::Host;
+use chain_reaction::record::{GameRecord, SavedGame};
+use chain_reaction::stats::GameStats;
+
+use crate::args::{Options, WATCH_AI};
+use crate::command::{self, Command, HELP};
+use crate::playback;
+use crate::render::{self, Renderer};
+
+const HINT_AI: &str = "minimax";
+
+fn get_input(player: &str) -> Option<Command> {
+    let mut input = String::new();
+    loop {
+        println!("{} input:", player);
+        input.clear();
+        match std::io::stdin().read_line(&mut input) {
+            Ok(0) => return None,
+            Ok(_) => match command::parse(&input) {
+                Ok(command) => return Some(command),
+                Err(err) => println!("{}", err),
+            },
+            Err(err) => {
+                println!("cannot read input: {}", err);
+                return None;
+            }
+        }
+    }
+}
+
+fn describe(err: &MoveError) -> &'static str {
+    match err {
+        MoveError::OtherPlayersCell => "cell belongs to another player",
+        MoveError::NotCurrentPlayerMove => "it is not your turn",
+        MoveError::MoveOutsideBoard => "cell is outside the board",
+        MoveError::MoveNotComplete => "previous move is still exploding",
+        MoveError::GameOver => "game is over",
+        MoveError::PlayerOut => "player is out of the game",
+        MoveError::OutOfTime => "player has no time left",
+        MoveError::NoDrawOffer => "no draw is offered",
+    }
+}
+
+pub fn load_record(path: &str) -> Result<(GameRecord, Board), String> {
+    let text =
+        std::fs::read_to_string(path).map_err(|err| format!("cannot read {}: {}", path, err))?;
+    let saved =
+        SavedGame::parse(&text).map_err(|err| format!("invalid game {}: {:?}", path, err))?;
+    Ok((saved.record, saved.board))
+}
+
+fn stats(record: &GameRecord) -> Result<GameStats, String> {
+    GameStats::from_record(record).map_err(|err| format!("cannot replay game: {:?}", err))
+}
+
+/// Who plays for a seat.
+enum Seat {
+    Human,
+    Computer(Box<dyn Player>),
+    Engine(Host),
+}
+
+struct Game {
+    options: Options,
+    record: GameRecord,
+    board: Board,
+    stats: GameStats,
+    seats: Vec<Seat>,
+    redo: Vec<(u8, usize, usize)>,
+    renderer: Renderer,
+}
+
+impl Game {
+    fn new(options: Options) -> Result<Self, String> {
+        let (record, board) = match options.load {
+            Some(ref path) => load_record(path)?,
+            None => {
+                let mut record =
+                    GameRecord::new(options.rows, options.cols, options.players, options.rules);
+                record.teams = options.teams;
+                let mut board =
+                    Board::with_rules(options.rows, options.cols, options.players, options.rules);
+                if let Some(teams) = options.teams {
+                    board = board.with_teams(teams);
+                }
+                (record, board)
+            }
+        };
+        let renderer = Renderer::new(options.color);
+        let stats = stats(&record)?;
+        let mut game = Self {
+            options,
+            record,
+            board,
+            stats,
+            seats: vec![],
+            redo: vec![],
+            renderer,
+        };
+        game.assign_seats()?;
+        Ok(game)
+    }
+
+    fn assign_seats(&mut self) -> Result<(), String> {
+        let record = &self.record;
+        let mut seats: Vec<Seat> = (0..record.players).map(|_| Seat::Human).collect();
+        for (seat, name) in self.options.ais.iter() {
+            let ai = ai::from_name(name, self.options.seed.wrapping_add(*seat as u64))
+                .ok_or_else(|| format!("unknown computer player {}", name))?;
+            match seats.get_mut(*seat as usize) {
+                Some(slot) => *slot = Seat::Computer(ai),
+                None => return Err(format!("seat {} does not exist", seat)),
+            }
+        }
+        for (seat, command) in self.options.engines.iter() {
+            let slot = seats
+                .get_mut(*seat as usize)
+                .ok_or_else(|| format!("seat {} does not exist", seat))?;
+            let failed = |err| format!("engine `{}` failed: {:?}", command, err);
+            let mut host = Host::spawn(command).map_err(failed)?;
+            host.new_game(record.rows, record.cols, record.players, record.rules)
+                .map_err(failed)?;
+            *slot = Seat::Engine(host);
+        }
+        if self.options.watch {
+            for (seat, slot) in seats.iter_mut().enumerate() {
+                if matches!(slot, Seat::Human) {
+                    let seed = self.options.seed.wrapping_add(seat as u64);
+                    let ai = ai::from_name(WATCH_AI, seed).expect("watch player exists");
+                    *slot = Seat::Computer(ai);
+                }
+            }
+        }
+        self.seats = seats;
+        Ok(())
+    }
+
+    /// Lists players of team.
+    fn team(&self, team: u8) -> String {
+        (0..self.board.player_count())
+            .filter(|p| self.board.team(*p) == team)
+            .map(|p| self.renderer.player(p))
+            .collect::<Vec<_>>()
+            .join(" and ")
+    }
+
+    fn is_human(&self, player: u8) -> bool {
+        matches!(self.seats[player as usize], Seat::Human)
+    }
+
+    /// Writes game along with whose turn it is and eliminated players.
+    fn save_record(&self, path: &str) -> Result<(), String> {
+        let mut board = self.board.clone();
+        board.settle();
+        let saved = SavedGame {
+            record: self.record.clone(),
+            board,
+        };
+        std::fs::write(path, saved.to_string())
+            .map_err(|err| format!("cannot write {}: {}", path, err))
+    }
+
+    fn record_changed(&self) -> Result<(), String> {
+        match self.options.record {
+            Some(ref path) => self.save_record(path),
+            None => Ok(()),
+        }
+    }
+
+    fn play_move(&mut self, player: u8, row: usize, col: usize) -> Result<(), String> {
+        match self.board.player_move(player, row, col) {
+            Ok(()) => {
+                self.record.moves.push((player, row, col));
+                self.stats.moved(player, &self.board);
+                self.record_changed()
+            }
+            Err(err) => {
+                println!("{} {}: {}", row, col, describe(&err));
+                Ok(())
+            }
+        }
+    }
+
+    fn replay(&mut self) -> Result<(), String> {
+        self.board = self
+            .record
+            .replay()
+            .map_err(|err| format!("cannot replay game: {:?}", err))?;
+        self.stats = stats(&self.record)?;
+        self.record_changed()
+    }
+
+    /// Take back moves till it is a human player's turn.
+    fn undo(&mut self) -> Result<(), String> {
+        if self.record.moves.is_empty() {
+            println!("nothing to undo");
+            return Ok(());
+        }
+        while let Some(undone) = self.record.moves.pop() {
+            self.redo.push(undone);
+            if self.is_human(undone.0) {
+                break;
+            }
+        }
+        let resigned = self.record.resigned.len();
+        self.record.truncate(self.record.moves.len());
+        if resigned != self.record.resigned.len() {
+            // Moves taken back followed resignation, which is taken back too.
+            self.redo.clear();
+        }
+        self.replay()
+    }
+
+    /// Play again moves taken back till it is a human player's turn.
+    fn redo(&mut self) -> Result<(), String> {
+        if self.redo.is_empty() {
+            println!("nothing to redo");
+            return Ok(());
+        }
+        while let Some(redone) = self.redo.pop() {
+            self.record.moves.push(redone);
+            match self.redo.last() {
+                Some(next) if !self.is_human(next.0) => {}
+                _ => break,
+            }
+        }
+        self.replay()
+    }
+
+    fn history(&self) {
+        if self.record.moves.is_empty() {
+            println!("no moves yet");
+        }
+        for (number, (player, row, col)) in self.record.moves.iter().enumerate() {
+            println!("{:>3}. player {}: {} {}", number + 1, player, row, col);
+        }
+    }
+
+    fn hint(&self) {
+        let mut ai = ai::from_name(HINT_AI, self.options.seed).expect("hint player exists");
+        if let Some((row, col)) = ai.choose_move(&self.board) {
+            println!("hint: {} {}", row, col);
+        }
+    }
+
+    fn load(&mut self, path: &str) -> Result<(), String> {
+        let (record, board) = load_record(path)?;
+        let previous = std::mem::replace(&mut self.record, record);
+        if let Err(err) = self.assign_seats() {
+            self.record = previous;
+            self.assign_seats()?;
+            return Err(err);
+        }
+        self.board = board;
+        self.stats = stats(&self.record)?;
+        self.redo.clear();
+        self.record_changed()
+    }
+
+    /// Offers, accepts or declines draw for player, recording draw once
+    /// everyone agreed.
+    fn draw(
+        &mut self,
+        player: u8,
+        action: fn(&mut Board, u8) -> Result<(), MoveError>,
+        done: &str,
+    ) -> Result<(), String> {
+        if let Err(err) = action(&mut self.board, player) {
+            println!("{}", describe(&err));
+            return Ok(());
+        }
+        println!("{} {}", self.renderer.player(player), done);
+        if self.board.is_over() {
+            self.record.drawn = true;
+            self.redo.clear();
+            self.record_changed()?;
+        }
+        Ok(())
+    }
+
+    /// Runs command of human player, returns `false` when game should end.
+    fn run_command(&mut self, player: u8, command: Command) -> Result<bool, String> {
+        match command {
+            Command::Move(row, col) => {
+                let moves = self.record.moves.len();
+                self.play_move(player, row, col)?;
+                if moves != self.record.moves.len() {
+                    self.redo.clear();
+                }
+            }
+            Command::Undo => self.undo()?,
+            Command::Redo => self.redo()?,
+            Command::Hint => self.hint(),
+            Command::Save(path) => match self.save_record(&path) {
+                Ok(()) => println!("saved to {}", path),
+                Err(err) => println!("{}", err),
+            },
+            Command::Load(path) => match self.load(&path) {
+                Ok(()) => println!("loaded {}", path),
+                Err(err) => println!("{}", err),
+            },
+            Command::History => self.history(),
+            Command::Resign => match self.board.resign(player) {
+                // Game goes on while other players remain.
+                Ok(()) => {
+                    println!("{} resigned", self.renderer.player(player));
+                    self.record.resigned.push((self.record.moves.len(), player));
+                    self.redo.clear();
+                    self.record_changed()?;
+                }
+                Err(err) => println!("{}", describe(&err)),
+            },
+            Command::OfferDraw | Command::AcceptDraw | Command::DeclineDraw
+                if (0..self.board.player_count())
+                    .any(|p| self.board.is_player_alive(p) && !self.is_human(p)) =>
+            {
+                println!("computer players do not take draws");
+            }
+            Command::OfferDraw => self.draw(player, Board::offer_draw, "offers a draw")?,
+            Command::AcceptDraw => self.draw(player, Board::accept_draw, "accepts the draw")?,
+            Command::DeclineDraw => self.draw(player, Board::decline_draw, "declines the draw")?,
+            Command::Help => println!("{}", HELP),
+            Command::Quit => {
+                if let Some(ref path) = self.options.save {
+                    self.save_record(path)?;
+                    println!("saved to {}, continue with `--load {}`", path, path);
+                }
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+pub fn play(options: Options) -> Result<(), String> {
+    let mut game = Game::new(options)?;
+    if let Some(teams) = game.board.teams() {
+        let teams: Vec<String> = (0..teams.count).map(|team| game.team(team)).collect();
+        println!("teams: {}", teams.join(" against "));
+    }
+    let mut show = true;
+    let winner = loop {
+        let settled = matches!(
+            game.board.state(),
+            BoardState::Wait | BoardState::GameOver(_) | BoardState::Draw
+        );
+        if show && settled {
+            println!("{}", game.renderer.board(&game.board));
+        }
+        show = true;
+        match game.board.state() {
+            BoardState::GameOver(player) => {
+                break Some(*player);
+            }
+            BoardState::Draw => break None,
+            BoardState::Wait => {
+                let cur_player = game.board.current_player_id();
+                match game.seats[cur_player as usize] {
+                    Seat::Computer(ref mut ai) => {
+                        std::thread::sleep(game.options.delay);
+                        let (x, y) = ai
+                            .choose_move(&game.board)
+                            .ok_or("computer player found no move")?;
+                        let player = game.renderer.player(cur_player);
+                        println!("{} ({}) plays {} {}", player, ai.name(), x, y);
+                        game.play_move(cur_player, x, y)?;
+                    }
+                    Seat::Engine(ref mut host) => {
+                        std::thread::sleep(game.options.delay);
+                        let (x, y) = host
+                            .best_move(&game.record.turns(), game.options.engine_time)
+                            .map_err(|err| format!("engine `{}` failed: {:?}", host.name(), err))?;
+                        if !game.board.valid_moves().contains(&(x, y)) {
+                            return Err(format!(
+                                "engine `{}` played invalid move {} {}",
+                                host.name(),
+                                x,
+                                y
+                            ));
+                        }
+                        let player = game.renderer.player(cur_player);
+                        println!("{} ({}) plays {} {}", player, host.name(), x, y);
+                        game.play_move(cur_player, x, y)?;
+                    }
+                    Seat::Human => {
+                        let player = game.renderer.player(cur_player);
+                        let offers = game.board.draw_offers();
+                        if !offers.is_empty() && !offers.contains(&cur_player) {
+                            let offered: Vec<String> =
+                                offers.iter().map(|p| game.renderer.player(*p)).collect();
+                            println!(
+                                "draw offered by {}, `accept` or `decline`",
+                                offered.join(" and ")
+                            );
+                        }
+                        let command = get_input(&player).unwrap_or(Command::Quit);
+                        show = matches!(
+                            command,
+                            Command::Move(..) | Command::Undo | Command::Redo | Command::Load(_)
+                        );
+                        if !game.run_command(cur_player, command)? {
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+            _ => {
+                let stats = &mut game.stats;
+                playback::explode(
+                    &mut game.board,
+                    &game.renderer,
+                    game.options.playback,
+                    |board| stats.iterated(board),
+                )
+            }
+        }
+    };
+    match (winner, game.board.teams()) {
+        (None, _) => println!("game drawn"),
+        (Some(player), Some(_)) => println!("{} won", game.team(game.board.team(player))),
+        (Some(player), None) => println!("{} won", game.renderer.player(player)),
+    }
+    if game.board.player_count() > 2 {
+        let renderer = &game.renderer;
+        println!(
+            "{}",
+            render::standings(&game.board.standings(), |p| renderer.player(p))
+        );
+    }
+    println!(
+        "{}",
+        render::stats(&game.stats, |p| game.renderer.player(p))
+    );
+    Ok(())
+}
@@ -1,43 +1,51 @@
-use chain_reaction::board::{Board, BoardState};
+mod args;
+mod client;
+mod command;
+mod game;
+mod playback;
+mod puzzle;
+mod render;
+mod replay;
+mod tournament;
 
-fn get_input(player: u8) -> (usize, usize) {
-    let mut input = String::new();
-    loop {
-        println!("{} input:", player);
-        std::io::stdin()
-            .read_line(&mut input)
-            .expect("Not a valid string");
-        if let Some((xin, yin)) = input.split_once(' ') {
-            if let Ok(x) = xin.trim().parse() {
-                if let Ok(y) = yin.trim().parse() {
-                    return (x, y);
-                }
-            }
-        }
-        println!("parsing failed {}", input);
-        input.clear();
-    }
-}
+use args::{Command, USAGE};
+use chain_reaction::ai;
+use chain_reaction::engine::Engine;
 
 fn main() {
-    let mut board = Board::new(10, 10, 2);
-    let player = loop {
-        println!("{}", board);
-        match board.state() {
-            BoardState::GameOver(player) => {
-                break player;
-            }
-            BoardState::Wait => {
-                let cur_player = board.current_player_id();
-                let (x, y) = get_input(cur_player);
-                if let Err(x) = board.player_move(cur_player, x, y) {
-                    println!("{:?}", x);
-                }
-            }
-            _ => {
-                board.next_iteration();
-            }
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let result = match args::parse(&args) {
+        Ok(Command::Play(options)) => game::play(options),
+        Ok(Command::Puzzle { path, color }) => {
+            puzzle::run(&path, color);
+            Ok(())
+        }
+        Ok(Command::Replay { path, color }) => replay::run(&path, color),
+        Ok(Command::Tournament(tournament)) => {
+            tournament::run(tournament);
+            Ok(())
         }
+        Ok(Command::Engine { ai, seed }) => {
+            let player = ai::from_name(&ai, seed).expect("engine player exists");
+            Engine::new(player)
+                .run(std::io::stdin().lock(), std::io::stdout().lock())
+                .map_err(|err| format!("engine failed: {}", err))
+        }
+        Ok(Command::Connect {
+            address,
+            name,
+            room,
+            color,
+        }) => client::run(&address, name, room, color),
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Err(err) => Err(format!("{}\n{}", err, USAGE)),
     };
-    println!("{} player won", player);
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        std::process::exit(2);
+    }
 }
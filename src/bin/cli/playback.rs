@@ -0,0 +1,154 @@
+use chain_reaction::board::{Board, BoardState};
+use chain_reaction::record::column_letters;
+use std::io::{IsTerminal, Write};
+use std::time::Duration;
+
+use crate::render::Renderer;
+
+pub const WAVE_DELAY: Duration = Duration::from_millis(300);
+
+/// How explosion waves of a move are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Playback {
+    /// Only board after explosions are over.
+    Instant,
+    /// Every wave, waiting for enter before next one.
+    Step,
+    /// Every wave, pausing between them.
+    Timed(Duration),
+}
+
+impl Playback {
+    /// Provides playback by name, `timed` pauses for `delay`.
+    pub fn from_name(name: &str, delay: Duration) -> Option<Self> {
+        match name {
+            "instant" => Some(Self::Instant),
+            "step" => Some(Self::Step),
+            "timed" => Some(Self::Timed(delay)),
+            _ => None,
+        }
+    }
+}
+
+/// Describes explosion wave, e.g. `wave 2: a1 b2 exploding`.
+pub fn label(wave: usize, cells: &[(usize, usize)]) -> String {
+    let cells: Vec<String> = cells
+        .iter()
+        .map(|(row, col)| format!("{}{}", column_letters(*col), row + 1))
+        .collect();
+    format!("wave {}: {} exploding", wave, cells.join(" "))
+}
+
+/// Shows frames of explosion waves as per playback.
+///
+/// On terminals each frame is drawn over previous one and last one is
+/// cleared when animation is over, so only settled board stays on screen.
+pub struct Animation {
+    playback: Playback,
+    in_place: bool,
+    lines: usize,
+}
+
+impl Animation {
+    pub fn new(playback: Playback) -> Self {
+        Self {
+            playback,
+            in_place: std::io::stdout().is_terminal(),
+            lines: 0,
+        }
+    }
+
+    /// Moves cursor up over last frame and clears everything below.
+    fn clear(&mut self) {
+        if self.in_place && self.lines > 0 {
+            print!("\x1b[{}A\x1b[J", self.lines);
+        }
+        self.lines = 0;
+    }
+
+    /// Shows frame, waiting after it as per playback.
+    pub fn frame(&mut self, frame: &str) {
+        if self.playback == Playback::Instant {
+            return;
+        }
+        self.clear();
+        self.lines = frame.lines().count();
+        match self.playback {
+            Playback::Step => {
+                print!("{}, press enter", frame);
+                let _ = std::io::stdout().flush();
+                let _ = std::io::stdin().read_line(&mut String::new());
+            }
+            Playback::Timed(delay) => {
+                println!("{}", frame);
+                std::thread::sleep(delay);
+            }
+            Playback::Instant => {}
+        }
+    }
+
+    /// Clears last frame.
+    pub fn finish(&mut self) {
+        self.clear();
+    }
+}
+
+/// Runs explosions of last move till board settles, showing waves as per playback.
+///
+/// `iterated` is called with board after each step of explosions.
+pub fn explode(
+    board: &mut Board,
+    renderer: &Renderer,
+    playback: Playback,
+    mut iterated: impl FnMut(&Board),
+) {
+    let mut animation = Animation::new(playback);
+    let mut wave = 0;
+    while let BoardState::Explosion(cells) = board.state() {
+        wave += 1;
+        animation.frame(&format!("{}{}", renderer.board(board), label(wave, cells)));
+        board.next_iteration();
+        iterated(board);
+    }
+    animation.finish();
+    while board.next_iteration() {
+        iterated(board);
+    }
+    iterated(board);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        let delay = Duration::from_millis(10);
+        assert_eq!(Playback::from_name("step", delay), Some(Playback::Step));
+        assert_eq!(
+            Playback::from_name("timed", delay),
+            Some(Playback::Timed(delay))
+        );
+        assert_eq!(Playback::from_name("slow", delay), None);
+    }
+
+    #[test]
+    fn wave_label() {
+        assert_eq!(label(2, &[(0, 0), (1, 3)]), "wave 2: a1 d2 exploding");
+    }
+
+    #[test]
+    fn explode_settles_board() {
+        let mut board = Board::new(3, 3, 2);
+        for (player, row, col) in [(0, 0, 0), (1, 2, 2), (0, 0, 0)] {
+            board.player_move(player, row, col).unwrap();
+        }
+        let mut steps = 0;
+        explode(&mut board, &Renderer::new(false), Playback::Instant, |_| {
+            steps += 1
+        });
+        assert_eq!(steps, 2);
+        assert_eq!(board.state(), &BoardState::Wait);
+        assert_eq!(board.current_player_id(), 1);
+    }
+}
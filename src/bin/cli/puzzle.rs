@@ -0,0 +1,93 @@
+use chain_reaction::puzzle::{parse, Puzzle};
+
+use crate::render::Renderer;
+
+enum Command {
+    Move(usize, usize),
+    Next,
+    Previous,
+    Quit,
+}
+
+fn get_command() -> Option<Command> {
+    let mut input = String::new();
+    loop {
+        println!("move (row col), next, prev or quit:");
+        input.clear();
+        if std::io::stdin().read_line(&mut input).ok()? == 0 {
+            return None;
+        }
+        match input.trim() {
+            "next" | "n" => return Some(Command::Next),
+            "prev" | "p" => return Some(Command::Previous),
+            "quit" | "q" => return Some(Command::Quit),
+            line => {
+                if let Some((row, col)) = line.split_once(' ') {
+                    if let (Ok(row), Ok(col)) = (row.trim().parse(), col.trim().parse()) {
+                        return Some(Command::Move(row, col));
+                    }
+                }
+            }
+        }
+        println!("parsing failed {}", input.trim());
+    }
+}
+
+fn show(renderer: &Renderer, puzzle: &Puzzle, index: usize, total: usize) {
+    println!("Puzzle {}/{}: {}", index + 1, total, puzzle.name());
+    println!(
+        "{} to {}",
+        renderer.player(puzzle.board().current_player_id()),
+        puzzle.goal()
+    );
+    println!("{}", renderer.board(puzzle.board()));
+}
+
+pub fn run(path: &str, color: bool) {
+    let renderer = Renderer::new(color);
+    let puzzles = match std::fs::read_to_string(path) {
+        Ok(text) => match parse(&text) {
+            Ok(puzzles) if !puzzles.is_empty() => puzzles,
+            Ok(_) => {
+                eprintln!("no puzzles in {}", path);
+                return;
+            }
+            Err(err) => {
+                eprintln!("invalid puzzle file {}: {:?}", path, err);
+                return;
+            }
+        },
+        Err(err) => {
+            eprintln!("cannot read {}: {}", path, err);
+            return;
+        }
+    };
+    let mut index = 0;
+    show(&renderer, &puzzles[index], index, puzzles.len());
+    while let Some(command) = get_command() {
+        match command {
+            Command::Move(row, col) => match puzzles[index].check(row, col) {
+                Ok(true) if index + 1 == puzzles.len() => {
+                    println!("Solved! All puzzles done.");
+                    return;
+                }
+                Ok(true) => {
+                    println!("Solved!");
+                    index += 1;
+                }
+                Ok(false) => {
+                    println!("Goal not achieved, try again.");
+                    continue;
+                }
+                Err(err) => {
+                    println!("{:?}", err);
+                    continue;
+                }
+            },
+            Command::Next => index = (index + 1).min(puzzles.len() - 1),
+            Command::Previous => index = index.saturating_sub(1),
+            Command::Quit => return,
+        }
+        show(&renderer, &puzzles[index], index, puzzles.len());
+    }
+}
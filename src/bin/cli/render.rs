@@ -0,0 +1,182 @@
+use chain_reaction::board::{Board, CellState, Standing};
+use chain_reaction::record::column_letters;
+use chain_reaction::stats::GameStats;
+use std::fmt::Write;
+use std::io::IsTerminal;
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const EXPLOSION: &str = "\x1b[1;30;103m";
+const EMPTY: &str = "\x1b[90m";
+/// Foreground colour of each player.
+const PLAYER_COLORS: [&str; 8] = [
+    "\x1b[31m", "\x1b[34m", "\x1b[32m", "\x1b[33m", "\x1b[35m", "\x1b[36m", "\x1b[91m", "\x1b[94m",
+];
+/// Background colour of each player for cells about to explode.
+const PLAYER_CRITICAL_COLORS: [&str; 8] = [
+    "\x1b[1;97;41m",
+    "\x1b[1;97;44m",
+    "\x1b[1;30;42m",
+    "\x1b[1;30;43m",
+    "\x1b[1;97;45m",
+    "\x1b[1;30;46m",
+    "\x1b[1;30;101m",
+    "\x1b[1;30;104m",
+];
+
+/// Colours are used only on terminals and when `NO_COLOR` is not set.
+pub fn color_supported() -> bool {
+    std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+}
+
+/// Lists places of players, one per line, with turn players went out in.
+pub fn standings(standings: &[Standing], name: impl Fn(u8) -> String) -> String {
+    standings
+        .iter()
+        .map(|standing| match standing.eliminated {
+            Some(turn) => format!(
+                "{}. {}, out on turn {}",
+                standing.place,
+                name(standing.player),
+                turn
+            ),
+            None => format!("{}. {}", standing.place, name(standing.player)),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Summarizes stats of each player, one per line.
+pub fn stats(stats: &GameStats, name: impl Fn(u8) -> String) -> String {
+    stats
+        .players()
+        .iter()
+        .enumerate()
+        .map(|(player, stats)| {
+            format!(
+                "{}: {} atoms, {} explosions, {} cells captured, \
+                 longest chain {} waves ({} cells), peak territory {} cells",
+                name(player as u8),
+                stats.atoms_placed,
+                stats.explosions,
+                stats.cells_captured,
+                stats.longest_chain.waves,
+                stats.longest_chain.cells,
+                stats.peak_territory
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders board for terminal with coordinates, optionally in colour.
+pub struct Renderer {
+    color: bool,
+}
+
+impl Renderer {
+    pub fn new(color: bool) -> Self {
+        Self { color }
+    }
+
+    fn paint(&self, out: &mut String, style: &str, text: &str) {
+        if self.color {
+            let _ = write!(out, "{}{}{}", style, text, RESET);
+        } else {
+            out.push_str(text);
+        }
+    }
+
+    /// Provides name of player in player's colour.
+    pub fn player(&self, player: u8) -> String {
+        let mut out = String::new();
+        let style = PLAYER_COLORS[player as usize % PLAYER_COLORS.len()];
+        self.paint(&mut out, style, &format!("player {}", player));
+        out
+    }
+
+    fn cell(&self, out: &mut String, cell: &CellState, critical: bool) {
+        match *cell {
+            CellState::Explosion => {
+                self.paint(out, EXPLOSION, if self.color { " ** " } else { " XX " })
+            }
+            CellState::Empty => self.paint(out, EMPTY, if self.color { "  · " } else { " .. " }),
+            CellState::NonEmpty(owner, atoms) => {
+                let index = owner as usize % PLAYER_COLORS.len();
+                if self.color {
+                    let style = if critical {
+                        PLAYER_CRITICAL_COLORS[index]
+                    } else {
+                        PLAYER_COLORS[index]
+                    };
+                    out.push(' ');
+                    self.paint(out, style, &format!(" {}", atoms));
+                    out.push(' ');
+                } else {
+                    let marker = if critical { '!' } else { ' ' };
+                    let _ = write!(out, " {}{}{}", owner, atoms, marker);
+                }
+            }
+        }
+    }
+
+    /// Provides board with column letters and row numbers.
+    ///
+    /// Cells one atom away from exploding are highlighted, or marked with
+    /// `!` when colours are off.
+    pub fn board(&self, board: &Board) -> String {
+        self.cells(&board.cells(), |row, col| board.critical_mass(row, col))
+    }
+
+    /// Provides cells like [`Renderer::board`], for boards known only by cells.
+    pub fn cells(
+        &self,
+        cells: &[Vec<CellState>],
+        critical_mass: impl Fn(usize, usize) -> u8,
+    ) -> String {
+        let mut out = String::new();
+        let cols = cells.first().map_or(0, |row| row.len());
+        let header: String = (0..cols)
+            .map(|col| format!(" {:<3}", column_letters(col)))
+            .collect();
+        self.paint(&mut out, BOLD, &format!("    {}", header.trim_end()));
+        out.push('\n');
+        for (row, cells) in cells.iter().enumerate() {
+            self.paint(&mut out, BOLD, &format!("{:>3} ", row + 1));
+            for (col, cell) in cells.iter().enumerate() {
+                let critical = matches!(cell, CellState::NonEmpty(_, atoms)
+                    if atoms + 1 == critical_mass(row, col));
+                self.cell(&mut out, cell, critical);
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_board() {
+        let mut board = Board::new(3, 4, 2);
+        board.player_move(0, 0, 0).unwrap();
+        board.player_move(1, 1, 1).unwrap();
+        assert_eq!(
+            Renderer::new(false).board(&board),
+            concat!(
+                "     a   b   c   d\n",
+                "  1  01! ..  ..  .. \n",
+                "  2  ..  11  ..  .. \n",
+                "  3  ..  ..  ..  .. \n",
+            )
+        );
+    }
+
+    #[test]
+    fn coloured_player() {
+        assert_eq!(Renderer::new(true).player(1), "\x1b[34mplayer 1\x1b[0m");
+        assert_eq!(Renderer::new(false).player(1), "player 1");
+    }
+}
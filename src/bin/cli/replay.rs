@@ -0,0 +1,270 @@
+use chain_reaction::board::{Board, BoardState, CellState};
+use chain_reaction::record::{column_letters, GameRecord, RecordError};
+
+use crate::game::load_record;
+use crate::render::Renderer;
+
+const HELP: &str = "next/n, prev/p for moves, wave/w, back/b for explosion waves, \
+                    <move> to jump, quit/q";
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    NextMove,
+    PreviousMove,
+    NextWave,
+    PreviousWave,
+    Jump(usize),
+    Quit,
+}
+
+fn parse(line: &str) -> Option<Command> {
+    match line.trim() {
+        "next" | "n" | "" => Some(Command::NextMove),
+        "prev" | "p" => Some(Command::PreviousMove),
+        "wave" | "w" => Some(Command::NextWave),
+        "back" | "b" => Some(Command::PreviousWave),
+        "quit" | "q" => Some(Command::Quit),
+        number => number.parse().ok().map(Command::Jump),
+    }
+}
+
+fn get_command() -> Option<Command> {
+    let mut input = String::new();
+    loop {
+        println!("{}:", HELP);
+        input.clear();
+        if std::io::stdin().read_line(&mut input).ok()? == 0 {
+            return None;
+        }
+        match parse(&input) {
+            Some(command) => return Some(command),
+            None => println!("unknown command {}", input.trim()),
+        }
+    }
+}
+
+/// Position in game, moves played and waves shown of last move's explosions.
+///
+/// Waves are `None` once explosions of last move are over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    moves: usize,
+    waves: Option<usize>,
+}
+
+struct Viewer {
+    record: GameRecord,
+    position: Position,
+    board: Board,
+}
+
+impl Viewer {
+    fn new(record: GameRecord) -> Self {
+        let board = Board::with_rules(record.rows, record.cols, record.players, record.rules);
+        Self {
+            record,
+            position: Position {
+                moves: 0,
+                waves: None,
+            },
+            board,
+        }
+    }
+
+    fn go(&mut self, position: Position) -> Result<(), RecordError> {
+        let board = match position.waves {
+            Some(waves) => self.record.replay_waves(position.moves, waves)?,
+            None => self.record.replay_moves(position.moves)?,
+        };
+        self.position = match board.state() {
+            BoardState::Explosion(_) => position,
+            _ => Position {
+                waves: None,
+                ..position
+            },
+        };
+        self.board = board;
+        Ok(())
+    }
+
+    /// Number of waves in explosions of given move.
+    fn waves(&self, moves: usize) -> Result<usize, RecordError> {
+        let mut board = self.record.replay_waves(moves, 0)?;
+        Ok(board.settle())
+    }
+
+    /// Provides position for command, `None` when outside the game.
+    fn target(&self, command: &Command) -> Result<Option<Position>, RecordError> {
+        let Position { moves, waves } = self.position;
+        let total = self.record.moves.len();
+        let settled = |moves| Position { moves, waves: None };
+        let target = match *command {
+            Command::NextMove => Some(settled(moves + 1)).filter(|_| moves < total),
+            Command::PreviousMove => match waves {
+                Some(_) => Some(settled(moves - 1)),
+                None => moves.checked_sub(1).map(settled),
+            },
+            Command::Jump(moves) => Some(settled(moves)).filter(|_| moves <= total),
+            Command::NextWave => match waves {
+                Some(waves) => Some(Position {
+                    moves,
+                    waves: Some(waves + 1),
+                }),
+                None if moves < total => Some(Position {
+                    moves: moves + 1,
+                    waves: Some(0),
+                }),
+                None => None,
+            },
+            Command::PreviousWave => match waves {
+                Some(0) => Some(settled(moves - 1)),
+                Some(waves) => Some(Position {
+                    moves,
+                    waves: Some(waves - 1),
+                }),
+                None if moves == 0 => None,
+                None => match self.waves(moves)? {
+                    0 => Some(settled(moves - 1)),
+                    waves => Some(Position {
+                        moves,
+                        waves: Some(waves - 1),
+                    }),
+                },
+            },
+            Command::Quit => None,
+        };
+        Ok(target)
+    }
+
+    fn show(&self, renderer: &Renderer) -> Result<(), RecordError> {
+        let Position { moves, waves } = self.position;
+        let total = self.record.moves.len();
+        match moves.checked_sub(1).map(|index| self.record.moves[index]) {
+            Some((player, row, col)) => {
+                let waves = match waves {
+                    Some(wave) => format!("wave {}/{}", wave + 1, self.waves(moves)?),
+                    None => format!("explosion waves: {}", self.waves(moves)?),
+                };
+                println!(
+                    "move {}/{}: {} at {}{}, {}",
+                    moves,
+                    total,
+                    renderer.player(player),
+                    column_letters(col),
+                    row + 1,
+                    waves
+                );
+            }
+            None => println!("start of game, {} moves", total),
+        }
+        println!("{}", renderer.board(&self.board));
+        for line in stats(&self.board, renderer) {
+            println!("{}", line);
+        }
+        match self.board.state() {
+            BoardState::GameOver(winner) => println!("{} won", renderer.player(*winner)),
+            BoardState::Draw => println!("game drawn"),
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Provides cells and atoms owned by each player.
+fn stats(board: &Board, renderer: &Renderer) -> Vec<String> {
+    let cells = board.cells();
+    (0..board.player_count())
+        .map(|player| {
+            let (owned, atoms) = cells
+                .iter()
+                .flatten()
+                .filter_map(|cell| match *cell {
+                    CellState::NonEmpty(owner, atoms) if owner == player => Some(atoms as usize),
+                    _ => None,
+                })
+                .fold((0, 0), |(owned, total), atoms| (owned + 1, total + atoms));
+            let status = if board.is_player_alive(player) {
+                ""
+            } else {
+                ", out"
+            };
+            format!(
+                "  {}: {} cells, {} atoms{}",
+                renderer.player(player),
+                owned,
+                atoms,
+                status
+            )
+        })
+        .collect()
+}
+
+pub fn run(path: &str, color: bool) -> Result<(), String> {
+    let (record, _) = load_record(path)?;
+    let renderer = Renderer::new(color);
+    let mut viewer = Viewer::new(record);
+    let invalid = |err| format!("invalid record {}: {:?}", path, err);
+    viewer.show(&renderer).map_err(invalid)?;
+    while let Some(command) = get_command() {
+        if command == Command::Quit {
+            break;
+        }
+        match viewer.target(&command).map_err(invalid)? {
+            Some(position) => {
+                viewer.go(position).map_err(invalid)?;
+                viewer.show(&renderer).map_err(invalid)?;
+            }
+            None => println!("no such move"),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chain_reaction::board::Rules;
+
+    fn viewer() -> Viewer {
+        let mut record = GameRecord::new(3, 3, 2, Rules::Classic);
+        record.moves = vec![(0, 0, 0), (1, 2, 2), (0, 0, 0), (1, 2, 2)];
+        Viewer::new(record)
+    }
+
+    fn step(viewer: &mut Viewer, command: Command) -> Position {
+        let position = viewer.target(&command).unwrap().unwrap();
+        viewer.go(position).unwrap();
+        viewer.position
+    }
+
+    #[test]
+    fn commands() {
+        assert_eq!(parse("n\n"), Some(Command::NextMove));
+        assert_eq!(parse("12"), Some(Command::Jump(12)));
+        assert_eq!(parse("skip"), None);
+    }
+
+    #[test]
+    fn steps_through_waves() {
+        let mut viewer = viewer();
+        step(&mut viewer, Command::Jump(2));
+        let waves = |waves| Position {
+            moves: 3,
+            waves: Some(waves),
+        };
+        assert_eq!(step(&mut viewer, Command::NextWave), waves(0));
+        assert_eq!(viewer.board.cells()[0][0], CellState::Explosion);
+        let settled = Position {
+            moves: 3,
+            waves: None,
+        };
+        assert_eq!(step(&mut viewer, Command::NextWave), settled);
+        assert_eq!(viewer.board.cells()[0][1], CellState::NonEmpty(0, 1));
+        assert_eq!(step(&mut viewer, Command::PreviousWave), waves(0));
+        assert_eq!(step(&mut viewer, Command::PreviousMove).moves, 2);
+        assert_eq!(viewer.board.state(), &BoardState::Wait);
+        assert_eq!(viewer.target(&Command::Jump(5)).unwrap(), None);
+        step(&mut viewer, Command::Jump(4));
+        assert_eq!(viewer.target(&Command::NextMove).unwrap(), None);
+    }
+}
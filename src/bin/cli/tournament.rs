@@ -0,0 +1,35 @@
+use chain_reaction::tournament::Tournament;
+
+pub fn run(tournament: Tournament) {
+    let report = match tournament.run() {
+        Ok(report) => report,
+        Err(err) => {
+            eprintln!("error: {:?}", err);
+            std::process::exit(2);
+        }
+    };
+    println!(
+        "{:<10} {:>6} {:>6} {:>8} {:>8}",
+        "ai", "games", "wins", "win rate", "elo"
+    );
+    for ai in report.ais.iter() {
+        println!(
+            "{:<10} {:>6} {:>6} {:>7.1}% {:>8.0}",
+            ai.name,
+            ai.games,
+            ai.wins,
+            ai.win_rate() * 100.0,
+            ai.elo
+        );
+    }
+    let unfinished = report.games.iter().filter(|g| g.winner.is_none()).count();
+    println!("games: {} ({} unfinished)", report.games.len(), unfinished);
+    println!(
+        "average game length: {:.1} moves",
+        report.average_game_length()
+    );
+    println!(
+        "average chain length: {:.2} waves",
+        report.average_chain_length()
+    );
+}
@@ -0,0 +1,133 @@
+use chain_reaction::board::{LeavePolicy, Rules, MAX_PLAYERS, MAX_SIZE, MIN_SIZE};
+use chain_reaction::clock::{TimeControl, TimeoutAction};
+use chain_reaction::net::{self, Rooms, Transport, GRACE};
+use std::net::TcpListener;
+use std::time::Duration;
+
+const USAGE: &str = "usage: server [options]
+
+options:
+  --bind <address>           address to listen on (default 127.0.0.1:7878)
+  --websocket <address>      address to listen on for browsers, e.g. 127.0.0.1:7879
+  --rows <rows>              rows in board, 3 to 20 (default 10)
+  --cols <cols>              columns in board, 3 to 20 (default 10)
+  --players <players>        number of players, 2 to 8 (default 2)
+  --rules <rules>            classic or wrap (default classic)
+  --grace <seconds>          time players have to reconnect before forfeiting (default 60)
+  --move-time <seconds>      time for each move (default untimed)
+  --game-time <seconds>      time for all moves of each player (default untimed)
+  --on-timeout <action>      forfeit, random or skip, for players out of time (default forfeit)
+  --on-leave <policy>        clear or neutral, for atoms of players leaving (default clear)
+  --help                     show this help
+
+board options are for default room, clients may create rooms with own boards";
+
+struct Options {
+    bind: String,
+    websocket: Option<String>,
+    rows: usize,
+    cols: usize,
+    players: u8,
+    rules: Rules,
+    grace: Duration,
+    time: TimeControl,
+    leave: LeavePolicy,
+}
+
+/// Parse command line arguments without program name, `None` for help.
+fn parse(args: &[String]) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        bind: "127.0.0.1:7878".to_string(),
+        websocket: None,
+        rows: 10,
+        cols: 10,
+        players: 2,
+        rules: Rules::Classic,
+        grace: GRACE,
+        time: TimeControl::default(),
+        leave: LeavePolicy::default(),
+    };
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        if flag == "--help" {
+            return Ok(None);
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", flag))?;
+        let invalid = || format!("invalid value for {}: {}", flag, value);
+        let seconds = || match value.parse() {
+            Ok(0) | Err(_) => Err(invalid()),
+            Ok(seconds) => Ok(Some(Duration::from_secs(seconds))),
+        };
+        match flag.as_str() {
+            "--bind" => options.bind = value.clone(),
+            "--websocket" => options.websocket = Some(value.clone()),
+            "--rows" => options.rows = value.parse().map_err(|_| invalid())?,
+            "--cols" => options.cols = value.parse().map_err(|_| invalid())?,
+            "--players" => options.players = value.parse().map_err(|_| invalid())?,
+            "--rules" => options.rules = Rules::from_name(value).ok_or_else(invalid)?,
+            "--grace" => options.grace = Duration::from_secs(value.parse().map_err(|_| invalid())?),
+            "--move-time" => options.time.per_move = seconds()?,
+            "--game-time" => options.time.per_game = seconds()?,
+            "--on-timeout" => {
+                options.time.on_timeout = TimeoutAction::from_name(value).ok_or_else(invalid)?
+            }
+            "--on-leave" => options.leave = LeavePolicy::from_name(value).ok_or_else(invalid)?,
+            _ => return Err(format!("unknown option {}", flag)),
+        }
+    }
+    let sizes = MIN_SIZE..=MAX_SIZE;
+    if !sizes.contains(&options.rows) || !sizes.contains(&options.cols) {
+        return Err(format!(
+            "board needs {} to {} rows and columns",
+            MIN_SIZE, MAX_SIZE
+        ));
+    }
+    if !(2..=MAX_PLAYERS).contains(&options.players) {
+        return Err(format!("players should be between 2 and {}", MAX_PLAYERS));
+    }
+    Ok(Some(options))
+}
+
+fn listen(address: &str) -> Result<TcpListener, String> {
+    TcpListener::bind(address).map_err(|err| format!("cannot listen on {}: {}", address, err))
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let mut listeners = vec![(listen(&options.bind)?, Transport::Lines)];
+    println!(
+        "hosting {}x{} {} games for {} players on {}",
+        options.rows,
+        options.cols,
+        options.rules.name(),
+        options.players,
+        options.bind
+    );
+    if let Some(ref address) = options.websocket {
+        listeners.push((listen(address)?, Transport::WebSocket));
+        println!("browsers connect to ws://{}", address);
+    }
+    let rooms = Rooms::new(options.rows, options.cols, options.players, options.rules)
+        .with_grace(options.grace)
+        .with_time(options.time)
+        .with_leave_policy(options.leave);
+    net::serve(listeners, rooms);
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match parse(&args) {
+        Ok(Some(options)) => run(options),
+        Ok(None) => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Err(err) => Err(format!("{}\n{}", err, USAGE)),
+    };
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        std::process::exit(2);
+    }
+}
@@ -0,0 +1,134 @@
+use chain_reaction::board::{Board, BoardState, MoveError, Rules};
+use chain_reaction::record::GameRecord;
+
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Place,
+    Undo,
+    Restart,
+    Quit,
+}
+
+pub struct App {
+    pub record: GameRecord,
+    pub board: Board,
+    pub cursor: (usize, usize),
+    pub message: String,
+}
+
+impl App {
+    pub fn new(rows: usize, cols: usize, players: u8, rules: Rules) -> Self {
+        Self {
+            record: GameRecord::new(rows, cols, players, rules),
+            board: Board::with_rules(rows, cols, players, rules),
+            cursor: (0, 0),
+            message: String::new(),
+        }
+    }
+
+    /// Explosion waves are still to be shown.
+    pub fn animating(&self) -> bool {
+        !matches!(
+            self.board.state(),
+            BoardState::Wait | BoardState::GameOver(_)
+        )
+    }
+
+    /// Shows next explosion wave.
+    pub fn tick(&mut self) {
+        self.board.next_iteration();
+    }
+
+    fn place(&mut self) {
+        let (row, col) = self.cursor;
+        let player = self.board.current_player_id();
+        self.message = match self.board.player_move(player, row, col) {
+            Ok(()) => {
+                self.record.moves.push((player, row, col));
+                String::new()
+            }
+            Err(MoveError::OtherPlayersCell) => "cell belongs to another player".to_string(),
+            Err(MoveError::GameOver) => "game is over, press r to restart".to_string(),
+            Err(err) => format!("{:?}", err),
+        };
+    }
+
+    fn undo(&mut self) {
+        if self.record.moves.pop().is_none() {
+            self.message = "nothing to undo".to_string();
+            return;
+        }
+        match self.record.replay() {
+            Ok(board) => {
+                self.board = board;
+                self.message.clear();
+            }
+            Err(err) => self.message = format!("cannot undo: {:?}", err),
+        }
+    }
+
+    /// Handles key press, returns `false` when app should quit.
+    pub fn handle_key(&mut self, key: Key) -> bool {
+        let (row, col) = self.cursor;
+        match key {
+            Key::Up => self.cursor.0 = row.saturating_sub(1),
+            Key::Down => self.cursor.0 = (row + 1).min(self.board.rows() - 1),
+            Key::Left => self.cursor.1 = col.saturating_sub(1),
+            Key::Right => self.cursor.1 = (col + 1).min(self.board.cols() - 1),
+            Key::Place if !self.animating() => self.place(),
+            Key::Undo if !self.animating() => self.undo(),
+            Key::Place | Key::Undo => {}
+            Key::Restart => {
+                let record = &self.record;
+                *self = Self::new(record.rows, record.cols, record.players, record.rules);
+            }
+            Key::Quit => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_stays_on_board() {
+        let mut app = App::new(3, 4, 2, Rules::Classic);
+        app.handle_key(Key::Up);
+        app.handle_key(Key::Left);
+        assert_eq!(app.cursor, (0, 0));
+        for _ in 0..5 {
+            app.handle_key(Key::Down);
+            app.handle_key(Key::Right);
+        }
+        assert_eq!(app.cursor, (2, 3));
+    }
+
+    #[test]
+    fn place_animate_and_undo() {
+        let mut app = App::new(3, 3, 2, Rules::Classic);
+        app.handle_key(Key::Place);
+        app.handle_key(Key::Down);
+        app.handle_key(Key::Place);
+        app.handle_key(Key::Up);
+        app.handle_key(Key::Place);
+        assert!(app.animating());
+        // Moves are ignored till explosion is over.
+        app.handle_key(Key::Undo);
+        assert_eq!(app.record.moves.len(), 3);
+        while app.animating() {
+            app.tick();
+        }
+        assert_eq!(app.board.state(), &BoardState::GameOver(0));
+        app.handle_key(Key::Undo);
+        assert_eq!(app.record.moves.len(), 2);
+        assert_eq!(app.board.state(), &BoardState::Wait);
+        assert!(app.handle_key(Key::Restart));
+        assert!(app.record.moves.is_empty());
+        assert!(!app.handle_key(Key::Quit));
+    }
+}
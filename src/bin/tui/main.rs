@@ -0,0 +1,141 @@
+mod app;
+mod ui;
+
+use app::{App, Key};
+use chain_reaction::board::{Rules, MAX_PLAYERS};
+use crossterm::cursor::{Hide, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use std::time::Duration;
+
+const USAGE: &str = "usage: tui [options]
+
+options:
+  --rows <rows>              rows in board, at least 3 (default 10)
+  --cols <cols>              columns in board, at least 3 (default 10)
+  --players <players>        number of players, 2 to 8 (default 2)
+  --rules <rules>            classic or wrap (default classic)
+  --help                     show this help";
+
+const WAVE_DELAY: Duration = Duration::from_millis(300);
+
+struct Options {
+    rows: usize,
+    cols: usize,
+    players: u8,
+    rules: Rules,
+}
+
+/// Parse command line arguments without program name, `None` for help.
+fn parse(args: &[String]) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        rows: 10,
+        cols: 10,
+        players: 2,
+        rules: Rules::Classic,
+    };
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        if flag == "--help" {
+            return Ok(None);
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", flag))?;
+        let invalid = || format!("invalid value for {}: {}", flag, value);
+        match flag.as_str() {
+            "--rows" => options.rows = value.parse().map_err(|_| invalid())?,
+            "--cols" => options.cols = value.parse().map_err(|_| invalid())?,
+            "--players" => options.players = value.parse().map_err(|_| invalid())?,
+            "--rules" => options.rules = Rules::from_name(value).ok_or_else(invalid)?,
+            _ => return Err(format!("unknown option {}", flag)),
+        }
+    }
+    if options.rows < 3 || options.cols < 3 {
+        return Err("board needs at least 3 rows and columns".to_string());
+    }
+    if !(2..=MAX_PLAYERS).contains(&options.players) {
+        return Err(format!("players should be between 2 and {}", MAX_PLAYERS));
+    }
+    Ok(Some(options))
+}
+
+/// Restores terminal when dropped, even on panic.
+struct Terminal;
+
+impl Terminal {
+    fn enter() -> std::io::Result<Self> {
+        enable_raw_mode()?;
+        execute!(std::io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = execute!(std::io::stdout(), Show, LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
+
+fn key(code: KeyCode) -> Option<Key> {
+    match code {
+        KeyCode::Up => Some(Key::Up),
+        KeyCode::Down => Some(Key::Down),
+        KeyCode::Left => Some(Key::Left),
+        KeyCode::Right => Some(Key::Right),
+        KeyCode::Enter | KeyCode::Char(' ') => Some(Key::Place),
+        KeyCode::Char('u') => Some(Key::Undo),
+        KeyCode::Char('r') => Some(Key::Restart),
+        KeyCode::Char('q') | KeyCode::Esc => Some(Key::Quit),
+        _ => None,
+    }
+}
+
+fn run(mut app: App) -> std::io::Result<()> {
+    let _terminal = Terminal::enter()?;
+    let mut out = std::io::stdout();
+    loop {
+        ui::draw(&mut out, &app)?;
+        // Explosion waves advance on their own, keys are still read to allow quitting.
+        if app.animating() && !event::poll(WAVE_DELAY)? {
+            app.tick();
+            continue;
+        }
+        if let Event::Key(event) = event::read()? {
+            if event.kind == KeyEventKind::Release {
+                continue;
+            }
+            if let Some(key) = key(event.code) {
+                if !app.handle_key(key) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match parse(&args) {
+        Ok(Some(options)) => run(App::new(
+            options.rows,
+            options.cols,
+            options.players,
+            options.rules,
+        ))
+        .map_err(|err| format!("terminal failed: {}", err)),
+        Ok(None) => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Err(err) => Err(format!("{}\n{}", err, USAGE)),
+    };
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        std::process::exit(2);
+    }
+}
@@ -0,0 +1,165 @@
+use chain_reaction::board::{BoardState, CellState};
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+use crossterm::style::{
+    Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
+};
+use crossterm::terminal::{Clear, ClearType};
+use std::io::Result;
+use std::io::Write;
+
+use crate::app::App;
+
+const CELL_WIDTH: u16 = 4;
+const PANEL_GAP: u16 = 4;
+const PLAYER_COLORS: [Color; 8] = [
+    Color::DarkRed,
+    Color::DarkBlue,
+    Color::DarkGreen,
+    Color::DarkYellow,
+    Color::DarkMagenta,
+    Color::DarkCyan,
+    Color::Red,
+    Color::Blue,
+];
+const HISTORY_LINES: usize = 10;
+const KEYS: &str = "arrows move  enter place  u undo  r restart  q quit";
+
+fn player_color(player: u8) -> Color {
+    PLAYER_COLORS[player as usize % PLAYER_COLORS.len()]
+}
+
+fn draw_board(out: &mut impl Write, app: &App) -> Result<()> {
+    for (row, cells) in app.board.cells().iter().enumerate() {
+        queue!(out, MoveTo(0, row as u16 + 2))?;
+        for (col, cell) in cells.iter().enumerate() {
+            if app.cursor == (row, col) {
+                queue!(out, SetAttribute(Attribute::Reverse))?;
+            }
+            match *cell {
+                CellState::Explosion => queue!(
+                    out,
+                    SetBackgroundColor(Color::Yellow),
+                    SetForegroundColor(Color::Black),
+                    Print(" ** ")
+                )?,
+                CellState::Empty => {
+                    queue!(out, SetForegroundColor(Color::DarkGrey), Print("  · "))?
+                }
+                CellState::NonEmpty(owner, atoms) => {
+                    if atoms + 1 == app.board.critical_mass(row, col) {
+                        queue!(out, SetAttribute(Attribute::Bold))?;
+                    }
+                    queue!(
+                        out,
+                        SetForegroundColor(player_color(owner)),
+                        Print(format!("  {} ", atoms))
+                    )?
+                }
+            }
+            queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+        }
+    }
+    Ok(())
+}
+
+fn draw_panel(out: &mut impl Write, app: &App) -> Result<()> {
+    let x = app.board.cols() as u16 * CELL_WIDTH + PANEL_GAP;
+    let cells = app.board.cells();
+    let mut line = 2;
+    queue!(
+        out,
+        MoveTo(x, line),
+        SetAttribute(Attribute::Bold),
+        Print("Players"),
+        SetAttribute(Attribute::Reset)
+    )?;
+    for player in 0..app.board.player_count() {
+        line += 1;
+        let owned = cells
+            .iter()
+            .flatten()
+            .filter(|cell| matches!(cell, CellState::NonEmpty(owner, _) if *owner == player))
+            .count();
+        let status = match app.board.state() {
+            BoardState::GameOver(winner) if *winner == player => "winner".to_string(),
+            _ if !app.board.is_player_alive(player) => "out".to_string(),
+            _ => format!("{} cells", owned),
+        };
+        let marker = if player == app.board.current_player_id() {
+            '>'
+        } else {
+            ' '
+        };
+        queue!(
+            out,
+            MoveTo(x, line),
+            Print(marker),
+            SetForegroundColor(player_color(player)),
+            Print(format!(" player {} ", player)),
+            ResetColor,
+            Print(status)
+        )?;
+    }
+    line += 2;
+    queue!(
+        out,
+        MoveTo(x, line),
+        SetAttribute(Attribute::Bold),
+        Print("History"),
+        SetAttribute(Attribute::Reset)
+    )?;
+    let moves = &app.record.moves;
+    for (number, (player, row, col)) in moves
+        .iter()
+        .enumerate()
+        .skip(moves.len().saturating_sub(HISTORY_LINES))
+    {
+        line += 1;
+        queue!(
+            out,
+            MoveTo(x, line),
+            Print(format!("{:>3}. ", number + 1)),
+            SetForegroundColor(player_color(*player)),
+            Print(format!("player {}", player)),
+            ResetColor,
+            Print(format!(" {} {}", row, col))
+        )?;
+    }
+    Ok(())
+}
+
+pub fn draw(out: &mut impl Write, app: &App) -> Result<()> {
+    let player = app.board.current_player_id();
+    let title = match app.board.state() {
+        BoardState::GameOver(_) => format!("Winner: player {}", player),
+        BoardState::Wait => format!("Current player: player {}", player),
+        _ => format!("Explosion by player {}", player),
+    };
+    queue!(
+        out,
+        Clear(ClearType::All),
+        MoveTo(0, 0),
+        SetForegroundColor(player_color(player)),
+        SetAttribute(Attribute::Bold),
+        Print(title),
+        SetAttribute(Attribute::Reset),
+        ResetColor
+    )?;
+    draw_board(out, app)?;
+    draw_panel(out, app)?;
+    // Below both board and panel of players and history.
+    let panel = app.board.player_count() as usize + HISTORY_LINES + 3;
+    let bottom = app.board.rows().max(panel) as u16 + 3;
+    queue!(
+        out,
+        MoveTo(0, bottom),
+        SetForegroundColor(Color::DarkRed),
+        Print(&app.message),
+        ResetColor,
+        MoveTo(0, bottom + 1),
+        Print(KEYS)
+    )?;
+    out.flush()?;
+    Ok(())
+}
@@ -3,18 +3,50 @@ use yew_router::prelude::*;
 
 use crate::board::GameBoard;
 use crate::menu::Menu;
+use crate::online::{OnlineMenu, Room};
+use crate::puzzle::PuzzleBoard;
+use crate::saved::{self, SavedGame};
+use crate::setup::Setup;
+use crate::shared::SharedGame;
 #[derive(Clone, Routable, PartialEq)]
 pub enum Route {
-    #[at("/:players")]
-    App { players: u8 },
+    #[at("/puzzle/:id")]
+    Puzzle { id: usize },
+    #[at("/online")]
+    Online,
+    #[at("/room/:code")]
+    Room { code: String },
+    #[at("/play/:setup")]
+    App { setup: Setup },
+    /// Game in compact notation of records.
+    #[at("/game/:game")]
+    Game { game: String },
+    #[at("/continue")]
+    Continue,
+    #[at("/finished/:index")]
+    Finished { index: usize },
     #[not_found]
     #[at("/")]
     Menu,
 }
 
+/// Board of saved game, menu if it is gone.
+fn saved_board(saved: Option<SavedGame>) -> Html {
+    match saved.as_ref().and_then(SavedGame::setup) {
+        Some(setup) => html! {<GameBoard {setup} {saved} />},
+        None => html! {<Redirect<Route> to={Route::Menu} />},
+    }
+}
+
 fn switch_route(routes: Route) -> Html {
     match routes {
-        Route::App { players } => html! {<GameBoard players={players} />},
+        Route::App { setup } => html! {<GameBoard {setup} />},
+        Route::Game { game } => html! {<SharedGame {game} />},
+        Route::Continue => saved_board(saved::current()),
+        Route::Finished { index } => saved_board(saved::finished().get(index).cloned()),
+        Route::Puzzle { id } => html! {<PuzzleBoard id={id} />},
+        Route::Online => html! { <OnlineMenu /> },
+        Route::Room { code } => html! {<Room code={code} />},
         Route::Menu => html! { <Menu /> },
     }
 }
@@ -1,5 +1,9 @@
-use chain_reaction::board::{Board, BoardState};
-use gloo_timers::callback::Timeout;
+use chain_reaction::board::{Board, BoardState, CellState, MoveError};
+use chain_reaction::net::{ClientMessage, ServerMessage};
+use chain_reaction::record::GameRecord;
+use chain_reaction::stats::GameStats;
+use gloo_timers::callback::{Interval, Timeout};
+use gloo_worker::{Spawnable, WorkerBridge};
 use std::cell::RefCell;
 use std::rc::Rc;
 use yew::prelude::*;
@@ -7,16 +11,143 @@ use yew_router::prelude::*;
 
 use crate::app::Route;
 use crate::cells::Cell;
+use crate::computer::{position, Answer, Computer, Question};
+use crate::online::{Connection, Online, Remote};
+use crate::results::Results;
+use crate::saved::{self, SavedGame};
+use crate::setup::{Setup, HUMAN};
+
+/// Time each explosion wave is shown.
+const WAVE_MS: u32 = 1_000;
+
+/// Pause between moves when game is replayed.
+const REPLAY_MS: u32 = 700;
+
+/// Least pause before computer players move.
+const COMPUTER_MS: u32 = 500;
+
+/// Script of `worker` binary, where computer players think.
+const WORKER_PATH: &str = "worker.js";
+
+/// How often clocks of timed online games count down.
+const CLOCK_MS: u32 = 1_000;
+
+pub const BOARD_STYLE: &str = r#"
+        svg {width: 2rem; height: 2rem}
+        html {background-color: LightGray;}
+        .app {display: flex;align-items: center;flex-direction: column;}
+        .app table {border-collapse: collapse; font-size: 2.5em;}
+        .app td {
+            border: 1px solid white;
+            background-image: linear-gradient(LightSlateGray, Black, LightSlateGray);
+        }
+        .explosion {color: black}
+        @keyframes explode {
+          from {
+            transform: scale(0.5);
+          }
+          to {
+            opacity: 0.5;
+            transform: scale(1.5) rotate(10deg);
+          }
+        }
+        .explosion svg {
+           animation:  explode 1.5s ;
+        }
+        @keyframes dance {
+          0% {
+              transform-origin: center;
+              transform: rotate(-45deg);
+          }
+          100% {
+              transform-origin: center;
+              transform: rotate(45deg);
+          }
+        }
+        circle {
+           animation:  dance 2s infinite alternate;
+        }
+        "#;
+
+pub fn player_colors(players: u8) -> String {
+    (0..players)
+        .map(|p| {
+            format!(
+                r#".player-{p} {{color: hsl({h},50%,50%);}}
+                   .player-{p} circle {{ filter: hue-rotate({h}deg);}}
+                   .player-{p} button {{ font-size: 1.5rem;}}
+                "#,
+                p = p,
+                h = (p as usize) * 360 / (players as usize),
+            )
+        })
+        .collect()
+}
+
+pub fn cells_table(cells: &[Vec<CellState>], onclick: &Callback<(usize, usize)>) -> Html {
+    html! {
+        <table>{
+            cells.iter().enumerate().map(
+                |(r, row)| html!{<tr>{
+                    row.iter().enumerate().map(
+                        |(c, cell)|
+                        html!{
+                            <Cell
+                                state={cell.clone()}
+                                row={r}
+                                col={c}
+                                onclick={onclick.clone()}
+                            />
+                        }
+                    ).collect::<Html>()
+                }</tr>}
+            ).collect::<Html>()
+        }</table>
+    }
+}
 
 pub enum GameBoardAction {
     MoveAnimation,
     Move(usize, usize),
-    Reset(u8),
+    /// Computer players can be asked for moves.
+    Worker(WorkerBridge<Computer>),
+    /// Computer player of current seat chose move.
+    ComputerMoved(Answer),
+    Reset(Setup),
+    /// Local game continues from where it was saved.
+    Restore(SavedGame),
+    /// Local game is played again from the start, move by move.
+    Replay,
+    ReplayMove,
+    /// Current player of local game gives up.
+    Resign,
+    /// Current player of local game offers, accepts or declines draw.
+    Draw(fn(&mut Board, u8) -> Result<(), MoveError>),
+    /// Board is played on server instead, in room with code if known.
+    Connect(Option<String>),
+    Received(ServerMessage),
+    Closed,
+    /// Time passed on clock of online game.
+    Tick(u32),
 }
 
 pub struct GameBoardState {
     board: RefCell<Board>,
+    record: RefCell<GameRecord>,
+    stats: RefCell<GameStats>,
+    /// Player of each seat, [`HUMAN`] or name of computer player.
+    seats: RefCell<Vec<String>>,
+    worker: RefCell<Option<WorkerBridge<Computer>>>,
+    /// Number of last question to computer players.
+    asked: RefCell<usize>,
+    /// Answer to last question is awaited.
+    thinking: RefCell<bool>,
+    /// Moves, resignations and draw of local game last saved.
+    saved: RefCell<(usize, usize, bool)>,
+    /// Next move of record to be shown while replaying.
+    replaying: RefCell<Option<usize>>,
     error: RefCell<String>,
+    remote: RefCell<Option<Remote>>,
 }
 
 impl Reducible for GameBoardState {
@@ -24,18 +155,167 @@ impl Reducible for GameBoardState {
     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
         {
             let mut board = self.board.borrow_mut();
+            let mut remote = self.remote.borrow_mut();
+            let mut record = self.record.borrow_mut();
+            let mut stats = self.stats.borrow_mut();
+            let mut replaying = self.replaying.borrow_mut();
+            let mut seats = self.seats.borrow_mut();
+            let mut thinking = self.thinking.borrow_mut();
+            let computer = seats[board.current_player_id() as usize] != HUMAN;
+            let mut played = None;
             match action {
-                GameBoardAction::MoveAnimation => if !board.next_iteration() {},
+                GameBoardAction::MoveAnimation => match remote.as_mut() {
+                    Some(remote) => remote.next_wave(),
+                    None => {
+                        board.next_iteration();
+                        stats.iterated(&board);
+                    }
+                },
+                GameBoardAction::Move(..) if replaying.is_some() || computer => {}
                 GameBoardAction::Move(r, c) => {
+                    played = Some(play(&mut board, &mut record, &mut stats, r, c))
+                }
+                GameBoardAction::Worker(worker) => *self.worker.borrow_mut() = Some(worker),
+                GameBoardAction::ComputerMoved(answer)
+                    if !*thinking || answer.number != *self.asked.borrow() => {}
+                GameBoardAction::ComputerMoved(Answer { chosen, .. }) => match chosen {
+                    Some((r, c)) => {
+                        *thinking = false;
+                        played = Some(play(&mut board, &mut record, &mut stats, r, c));
+                    }
+                    None => *self.error.borrow_mut() = "Computer found no move".to_string(),
+                },
+                GameBoardAction::Resign => {
                     let cur_player = board.current_player_id();
-                    if let Err(msg) = board.player_move(cur_player, r, c) {
-                        *self.error.borrow_mut() = format!("{:?}", msg);
-                    } else {
-                        self.error.borrow_mut().drain(..);
-                    };
+                    match board.resign(cur_player) {
+                        Ok(()) => {
+                            let moves = record.moves.len();
+                            record.resigned.push((moves, cur_player));
+                        }
+                        Err(msg) => *self.error.borrow_mut() = format!("{:?}", msg),
+                    }
+                }
+                GameBoardAction::Draw(answer) => {
+                    let cur_player = board.current_player_id();
+                    match answer(&mut board, cur_player) {
+                        Ok(()) => record.drawn = board.state() == &BoardState::Draw,
+                        Err(msg) => *self.error.borrow_mut() = format!("{:?}", msg),
+                    }
+                }
+                GameBoardAction::Reset(setup) => {
+                    *record = setup.record();
+                    *board = record.replay().expect("setup is valid");
+                    *stats = GameStats::new(setup.players());
+                    *seats = setup.seats;
+                    *replaying = None;
+                    *thinking = false;
+                    *self.saved.borrow_mut() = (0, 0, false);
+                }
+                GameBoardAction::Restore(game) => {
+                    let restored = game
+                        .record()
+                        .and_then(|restored| position(&restored).map(|played| (restored, played)));
+                    match restored {
+                        Some((restored, (restored_board, restored_stats))) => {
+                            *self.saved.borrow_mut() = (
+                                restored.moves.len(),
+                                restored.resigned.len(),
+                                restored.drawn,
+                            );
+                            *record = restored;
+                            *board = restored_board;
+                            *stats = restored_stats;
+                            *seats = game.seats;
+                            *replaying = None;
+                            *thinking = false;
+                        }
+                        None => *self.error.borrow_mut() = "Saved game is invalid".to_string(),
+                    }
+                }
+                GameBoardAction::Replay => {
+                    *board = record.replay_moves(0).expect("game was played");
+                    *stats = GameStats::new(record.players);
+                    *replaying = Some(0);
+                    *thinking = false;
+                }
+                GameBoardAction::ReplayMove if !matches!(board.state(), BoardState::Wait) => {}
+                GameBoardAction::ReplayMove => {
+                    if let Some(next) = *replaying {
+                        for player in record.resigned_after(next) {
+                            let _ = board.resign(player);
+                        }
+                        *replaying = match record.moves.get(next) {
+                            Some(&(player, row, col)) => {
+                                board
+                                    .player_move(player, row, col)
+                                    .expect("moves of game can be played again");
+                                stats.moved(player, &board);
+                                Some(next + 1)
+                            }
+                            None => {
+                                *board = record.replay().expect("game was played");
+                                None
+                            }
+                        };
+                    }
+                }
+                GameBoardAction::Connect(code) => {
+                    *remote = Some(Remote::new(code));
+                    self.error.borrow_mut().drain(..);
+                }
+                GameBoardAction::Received(message) => {
+                    if let Some(remote) = remote.as_mut() {
+                        remote.closed = false;
+                        *self.error.borrow_mut() = remote.receive(message).unwrap_or_default();
+                    }
+                }
+                GameBoardAction::Tick(ms) => {
+                    if let Some(remote) = remote.as_mut() {
+                        remote.tick(ms.into());
+                    }
                 }
-                GameBoardAction::Reset(players) => {
-                    *board = Board::new(10, 10, players);
+                GameBoardAction::Closed => {
+                    if let Some(remote) = remote.as_mut() {
+                        remote.closed = true;
+                    }
+                    *self.error.borrow_mut() = "Connection to server lost".to_string();
+                }
+            }
+            match played {
+                Some(Err(msg)) => *self.error.borrow_mut() = format!("{:?}", msg),
+                Some(Ok(())) => self.error.borrow_mut().clear(),
+                None => {}
+            }
+            let progress = (record.moves.len(), record.resigned.len(), record.drawn);
+            let over = board.is_over();
+            if remote.is_none()
+                && replaying.is_none()
+                && (over || matches!(board.state(), BoardState::Wait))
+                && *self.saved.borrow() != progress
+            {
+                *self.saved.borrow_mut() = progress;
+                let game = SavedGame {
+                    seats: seats.clone(),
+                    record: record.to_string(),
+                };
+                saved::store(game, over);
+            }
+            let cur_player = board.current_player_id() as usize;
+            if remote.is_none()
+                && replaying.is_none()
+                && !*thinking
+                && seats[cur_player] != HUMAN
+                && matches!(board.state(), BoardState::Wait)
+            {
+                if let Some(ref worker) = *self.worker.borrow() {
+                    let mut asked = self.asked.borrow_mut();
+                    *asked += 1;
+                    worker.send(Question {
+                        number: *asked,
+                        record: record.to_string(),
+                        computer: seats[cur_player].clone(),
+                    });
+                    *thinking = true;
                 }
             }
         }
@@ -43,132 +323,351 @@ impl Reducible for GameBoardState {
     }
 }
 
+/// Plays move of current player of local game.
+fn play(
+    board: &mut Board,
+    record: &mut GameRecord,
+    stats: &mut GameStats,
+    row: usize,
+    col: usize,
+) -> Result<(), MoveError> {
+    let player = board.current_player_id();
+    board.player_move(player, row, col)?;
+    record.moves.push((player, row, col));
+    stats.moved(player, board);
+    Ok(())
+}
+
+/// Connects board to server, sending first message once connected.
+fn open(
+    b: &UseReducerHandle<GameBoardState>,
+    connection: &RefCell<Option<Connection>>,
+    server: &str,
+    first: ClientMessage,
+) {
+    let received = {
+        let b = b.clone();
+        Callback::from(move |message| b.dispatch(GameBoardAction::Received(message)))
+    };
+    let closed = {
+        let b = b.clone();
+        Callback::from(move |_| b.dispatch(GameBoardAction::Closed))
+    };
+    match Connection::open(server, first, received, closed) {
+        Ok(opened) => *connection.borrow_mut() = Some(opened),
+        Err(_) => b.dispatch(GameBoardAction::Closed),
+    }
+}
+
 #[derive(Properties, PartialEq)]
 pub struct GameBoardPorps {
-    pub players: u8,
+    pub setup: Setup,
+    #[prop_or_default]
+    pub online: Option<Online>,
+    /// Game to continue instead of starting new one.
+    #[prop_or_default]
+    pub saved: Option<SavedGame>,
 }
 
 #[function_component(GameBoard)]
-pub fn game_board(GameBoardPorps { players }: &GameBoardPorps) -> Html {
+pub fn game_board(
+    GameBoardPorps {
+        setup,
+        online,
+        saved,
+    }: &GameBoardPorps,
+) -> Html {
     let navigator = use_navigator().unwrap();
     let back_to_menu = { Callback::from(move |_| navigator.push(&Route::Menu)) };
-    let game_board_state = use_reducer(|| GameBoardState {
-        board: RefCell::new(Board::new(10, 10, *players)),
-        error: RefCell::new(String::new()),
+    let game_board_state = use_reducer(|| {
+        let record = setup.record();
+        GameBoardState {
+            board: RefCell::new(record.replay().expect("setup is valid")),
+            record: RefCell::new(record),
+            stats: RefCell::new(GameStats::new(setup.players())),
+            seats: RefCell::new(setup.seats.clone()),
+            worker: RefCell::new(None),
+            asked: RefCell::new(0),
+            thinking: RefCell::new(false),
+            saved: RefCell::new((0, 0, false)),
+            replaying: RefCell::new(None),
+            error: RefCell::new(String::new()),
+            remote: RefCell::new(None),
+        }
     });
+    let connection = use_mut_ref(|| None::<Connection>);
     {
         let b = game_board_state.clone();
         use_effect_with_deps(
-            move |p| {
-                b.dispatch(GameBoardAction::Reset(*p));
+            move |_| {
+                let moved = b.clone();
+                let worker = Computer::spawner()
+                    .callback(move |answer| {
+                        let b = moved.clone();
+                        Timeout::new(COMPUTER_MS, move || {
+                            b.dispatch(GameBoardAction::ComputerMoved(answer))
+                        })
+                        .forget();
+                    })
+                    .spawn(WORKER_PATH);
+                b.dispatch(GameBoardAction::Worker(worker));
             },
-            *players,
+            (),
+        )
+    }
+    {
+        let b = game_board_state.clone();
+        use_effect_with_deps(
+            move |(setup, saved): &(Setup, Option<SavedGame>)| match saved {
+                Some(game) => b.dispatch(GameBoardAction::Restore(game.clone())),
+                None => b.dispatch(GameBoardAction::Reset(setup.clone())),
+            },
+            (setup.clone(), saved.clone()),
+        )
+    }
+    {
+        let b = game_board_state.clone();
+        let connection = connection.clone();
+        let players = setup.players();
+        use_effect_with_deps(
+            move |online: &Option<Online>| {
+                let mut clock = None;
+                if let Some(online) = online {
+                    b.dispatch(GameBoardAction::Connect(online.room.clone()));
+                    open(&b, &connection, &online.server, online.join(players));
+                    clock = Some(Interval::new(CLOCK_MS, move || {
+                        b.dispatch(GameBoardAction::Tick(CLOCK_MS))
+                    }));
+                }
+                move || {
+                    drop(connection.borrow_mut().take());
+                    drop(clock);
+                }
+            },
+            online.clone(),
         )
     }
     let onclick = {
         let b = game_board_state.clone();
-        Callback::from(move |(r, c): (usize, usize)| {
-            b.dispatch(GameBoardAction::Move(r, c));
-        })
+        let connection = connection.clone();
+        Callback::from(
+            move |(row, col): (usize, usize)| match *connection.borrow() {
+                Some(ref connection) => connection.send(&ClientMessage::Move { row, col }),
+                None => b.dispatch(GameBoardAction::Move(row, col)),
+            },
+        )
     };
-    let player_colors = (0..*players)
-        .map(|p| {
-            format!(
-                r#".player-{p} {{color: hsl({h},50%,50%);}}
-                   .player-{p} circle {{ filter: hue-rotate({h}deg);}}
-                   .player-{p} button {{ font-size: 1.5rem;}}
-                "#,
-                p = p,
-                h = (p as usize) * 360 / (*players as usize),
-            )
-        })
-        .collect::<String>();
 
     let reset = {
-        let players = *players;
+        let setup = setup.clone();
         let b = game_board_state.clone();
         Callback::from(move |_| {
-            b.dispatch(GameBoardAction::Reset(players));
+            b.dispatch(GameBoardAction::Reset(setup.clone()));
         })
     };
 
-    let (game_over, cur_player, cells, error) = {
-        let board = game_board_state.board.borrow_mut();
-        if !matches!(board.state(), BoardState::Wait | BoardState::GameOver(_)) {
-            let b = game_board_state.clone();
-            Timeout::new(1_000, move || b.dispatch(GameBoardAction::MoveAnimation)).forget();
-        }
-        (
-            matches!(board.state(), BoardState::GameOver(_)),
-            board.current_player_id(),
-            board.cells(),
-            game_board_state.error.borrow().clone(),
-        )
+    let reconnect = {
+        let b = game_board_state.clone();
+        let connection = connection.clone();
+        let online = online.clone();
+        let players = setup.players();
+        Callback::from(move |_| {
+            let Some(ref online) = online else {
+                return;
+            };
+            let rejoin = b.remote.borrow().as_ref().and_then(Remote::rejoin);
+            let first = rejoin.unwrap_or_else(|| online.join(players));
+            open(&b, &connection, &online.server, first);
+        })
     };
-    html! {
-        <>
-        <style>{player_colors}{r#"
-        svg {width: 2rem; height: 2rem}
-        html {background-color: LightGray;}
-        .app {display: flex;align-items: center;flex-direction: column;}
-        .app table {border-collapse: collapse; font-size: 2.5em;}
-        .app td {
-            border: 1px solid white;
-            background-image: linear-gradient(LightSlateGray, Black, LightSlateGray);
-        }
-        .explosion {color: black}
-        @keyframes explode {
-          from {
-            transform: scale(0.5);
-          }
-          to {
-            opacity: 0.5;
-            transform: scale(1.5) rotate(10deg);
-          }
+    let resign = {
+        let b = game_board_state.clone();
+        let connection = connection.clone();
+        Callback::from(move |_| match *connection.borrow() {
+            Some(ref connection) => connection.send(&ClientMessage::Resign),
+            None => b.dispatch(GameBoardAction::Resign),
+        })
+    };
+    let draw = |message: ClientMessage, answer: fn(&mut Board, u8) -> Result<(), MoveError>| {
+        let b = game_board_state.clone();
+        let connection = connection.clone();
+        Callback::from(move |_| match *connection.borrow() {
+            Some(ref connection) => connection.send(&message),
+            None => b.dispatch(GameBoardAction::Draw(answer)),
+        })
+    };
+    let offer_draw = draw(ClientMessage::OfferDraw, Board::offer_draw);
+    let accept_draw = draw(ClientMessage::AcceptDraw, Board::accept_draw);
+    let decline_draw = draw(ClientMessage::DeclineDraw, Board::decline_draw);
+    let replay = {
+        let b = game_board_state.clone();
+        Callback::from(move |_| b.dispatch(GameBoardAction::Replay))
+    };
+    let replaying = game_board_state.replaying.borrow().is_some();
+    let shared = {
+        let record = game_board_state.record.borrow();
+        (online.is_none() && !record.moves.is_empty()).then(|| record.to_notation())
+    };
+    let computer_turn = {
+        let board = game_board_state.board.borrow();
+        game_board_state.seats.borrow()[board.current_player_id() as usize] != HUMAN
+    };
+    let playing = match *game_board_state.remote.borrow() {
+        Some(ref remote) => remote.playing(),
+        None => {
+            !replaying
+                && !computer_turn
+                && matches!(game_board_state.board.borrow().state(), BoardState::Wait)
         }
-        .explosion svg {
-           animation:  explode 1.5s ;
+    };
+    // Draws are answered by player of client, or by current player of local
+    // game, where computer players take no draws.
+    let (draw_offers, answering) = match *game_board_state.remote.borrow() {
+        Some(ref remote) => (
+            remote
+                .state
+                .as_ref()
+                .map_or(vec![], |state| state.draw_offers.clone()),
+            remote.player,
+        ),
+        None => {
+            let board = game_board_state.board.borrow();
+            let humans = game_board_state
+                .seats
+                .borrow()
+                .iter()
+                .all(|seat| seat == HUMAN);
+            (
+                board.draw_offers(),
+                humans.then_some(board.current_player_id()),
+            )
         }
-        @keyframes dance {
-          0% {
-              transform-origin: center;
-              transform: rotate(-45deg);
-          }
-          100% {
-              transform-origin: center;
-              transform: rotate(45deg);
-          }
+    };
+    let drawing = match answering {
+        Some(player) if playing => Some(draw_offers.contains(&player)),
+        _ => None,
+    };
+    let closed = game_board_state
+        .remote
+        .borrow()
+        .as_ref()
+        .is_some_and(|remote| remote.closed);
+
+    let animate = || {
+        let b = game_board_state.clone();
+        Timeout::new(WAVE_MS, move || b.dispatch(GameBoardAction::MoveAnimation)).forget();
+    };
+    let mut results = html! {};
+    let (heading, cur_player, cells, error, player_count) = match *game_board_state.remote.borrow()
+    {
+        Some(ref remote) => {
+            if !remote.waves.is_empty() {
+                animate();
+            }
+            if let Some(ref state) = remote.state {
+                if state.is_over() && remote.waves.is_empty() {
+                    results = html! {
+                        <Results
+                            standings={state.standings.clone()}
+                            names={state.names.clone()}
+                            menu={back_to_menu.clone()}
+                        />
+                    };
+                }
+            }
+            let (heading, player) = remote.heading();
+            let player_count = remote
+                .state
+                .as_ref()
+                .map_or(setup.players(), |state| state.names.len() as u8);
+            (
+                heading,
+                player,
+                remote.cells(),
+                game_board_state.error.borrow().clone(),
+                player_count,
+            )
         }
-        circle {
-           animation:  dance 2s infinite alternate;
+        None => {
+            let board = game_board_state.board.borrow_mut();
+            if !matches!(board.state(), BoardState::Wait) && !board.is_over() {
+                animate();
+            } else if replaying && matches!(board.state(), BoardState::Wait) {
+                let b = game_board_state.clone();
+                Timeout::new(REPLAY_MS, move || b.dispatch(GameBoardAction::ReplayMove)).forget();
+            }
+            let name = |player: u8| match setup.seats.get(player as usize) {
+                Some(seat) if seat != HUMAN => format!("{} ({})", player, seat),
+                _ => player.to_string(),
+            };
+            if board.is_over() && !replaying {
+                let names = (0..board.player_count())
+                    .map(|p| format!("Player {}", name(p)))
+                    .collect::<Vec<_>>();
+                let stats = game_board_state.stats.borrow().clone();
+                results = html! {
+                    <Results
+                        standings={board.standings()}
+                        {names}
+                        stats={Some((stats, board.rows() * board.cols()))}
+                        rematch={Some(reset.clone())}
+                        replay={Some(replay.clone())}
+                        menu={back_to_menu.clone()}
+                    />
+                };
+            }
+            let cur_player = board.current_player_id();
+            let team = match board.teams() {
+                Some(_) => format!(" (team {})", board.team(cur_player)),
+                None => String::new(),
+            };
+            let heading = match board.state() {
+                BoardState::Draw => "Draw".to_string(),
+                BoardState::GameOver(_) => format!("Winner: {}{}", name(cur_player), team),
+                _ => format!("Current Player: {}{}", name(cur_player), team),
+            };
+            (
+                heading,
+                cur_player,
+                board.cells(),
+                game_board_state.error.borrow().clone(),
+                setup.players(),
+            )
         }
-        "#}</style>
+    };
+    let player_colors = player_colors(player_count);
+    html! {
+        <>
+        <style>{player_colors}{BOARD_STYLE}</style>
         <div class={classes!("app")}>
             <h1>{ "Chain Reaction" }</h1>
             <h2 class={classes!(format!("player-{}", cur_player))}>
-            {if game_over {"Winner: "} else {"Current Player: "} }{cur_player}{"  "}
+            {heading}{"  "}
             <button onclick={back_to_menu}>{"\u{1F519}"}</button>
-            <button onclick={reset}>{"\u{1F504}"}</button>
+            if online.is_none() {
+                <button onclick={reset}>{"\u{1F504}"}</button>
+            }
+            if playing {
+                <button onclick={resign} title="Resign">{"\u{1F3F3}"}</button>
+            }
+            if drawing == Some(false) && draw_offers.is_empty() {
+                <button onclick={offer_draw} title="Offer draw">{"\u{1F91D}"}</button>
+            }
+            if drawing == Some(false) && !draw_offers.is_empty() {
+                <button onclick={accept_draw} title="Accept draw">{"\u{2714}"}</button>
+                <button onclick={decline_draw} title="Decline draw">{"\u{2716}"}</button>
+            }
+            if closed {
+                <button onclick={reconnect}>{"Reconnect"}</button>
+            }
+            if let Some(game) = shared {
+                <Link<Route> to={Route::Game { game }}>{"\u{1F517}"}</Link<Route>>
+            }
             </h2>
             <p style="color: darkred;">{if !error.is_empty() {&error} else {""} }<br/></p>
-            <table>{
-                cells.iter().enumerate().map(
-                    |(r, row)| html!{<tr>{
-                        row.iter().enumerate().map(
-                            |(c, cell)|
-                            html!{
-                                <Cell
-                                    state={cell.clone()}
-                                    row={r}
-                                    col={c}
-                                    onclick={onclick.clone()}
-                                />
-                            }
-                        ).collect::<Html>()
-                    }</tr>}
-                ).collect::<Html>()
-            }</table>
+            {cells_table(&cells, &onclick)}
         </div>
+        {results}
         </>
     }
 }
@@ -0,0 +1,64 @@
+//! Computer players of web games, searching for moves in a Web Worker so
+//! the page stays responsive. Shared by `web` and `worker` binaries.
+
+use chain_reaction::ai;
+use chain_reaction::board::Board;
+use chain_reaction::record::GameRecord;
+use chain_reaction::stats::GameStats;
+use gloo_worker::{HandlerId, Worker, WorkerScope};
+use serde::{Deserialize, Serialize};
+
+/// Position of local game and computer player to move in it.
+#[derive(Serialize, Deserialize)]
+pub struct Question {
+    /// Told back with answer, so answers to old questions can be dropped.
+    pub number: usize,
+    /// Game record as text.
+    pub record: String,
+    /// Name of computer player.
+    pub computer: String,
+}
+
+/// Move chosen for question, `None` if there is none.
+#[derive(Serialize, Deserialize)]
+pub struct Answer {
+    pub number: usize,
+    pub chosen: Option<(usize, usize)>,
+}
+
+/// Plays moves and resignations of record on new board, collecting stats
+/// of game on the way.
+pub fn position(record: &GameRecord) -> Option<(Board, GameStats)> {
+    Some((record.replay().ok()?, GameStats::from_record(record).ok()?))
+}
+
+pub struct Computer;
+
+impl Worker for Computer {
+    type Message = ();
+    type Input = Question;
+    type Output = Answer;
+
+    fn create(_scope: &WorkerScope<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _scope: &WorkerScope<Self>, _msg: Self::Message) {}
+
+    fn received(&mut self, scope: &WorkerScope<Self>, question: Self::Input, id: HandlerId) {
+        let record = GameRecord::parse(&question.record).ok();
+        let moves = record.as_ref().map_or(0, |record| record.moves.len());
+        let chosen = record
+            .and_then(|record| position(&record))
+            .map(|(board, _)| board)
+            .zip(ai::from_name(&question.computer, moves as u64))
+            .and_then(|(board, mut computer)| computer.choose_move(&board));
+        scope.respond(
+            id,
+            Answer {
+                number: question.number,
+                chosen,
+            },
+        );
+    }
+}
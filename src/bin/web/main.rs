@@ -1,7 +1,14 @@
 mod app;
 pub mod board;
 pub mod cells;
+pub mod computer;
 pub mod menu;
+pub mod online;
+pub mod puzzle;
+pub mod results;
+pub mod saved;
+pub mod setup;
+pub mod shared;
 
 use app::App;
 
@@ -1,38 +1,176 @@
-use crate::app::Route;
-use web_sys::HtmlInputElement;
+use chain_reaction::ai;
+use chain_reaction::board::{Rules, Teams, MAX_PLAYERS, MAX_SIZE, MIN_SIZE};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 use yew_router::prelude::*;
 
+use crate::app::Route;
+use crate::saved;
+use crate::setup::{Setup, HUMAN};
+
+pub const MENU_STYLE: &str = r#"
+            .menu {
+                padding: 1rem;
+                display: flex;
+                align-items: center;
+                flex-direction: column;
+            }
+            .menu * {
+                margin: 1rem;
+                padding: 0.5rem;
+            }
+            "#;
+
+/// Team sizes offered in menu, games are played by two teams.
+const TEAM_SIZES: [u8; 2] = [2, 3];
+
+fn select_value(event: &Event) -> String {
+    event.target_unchecked_into::<HtmlSelectElement>().value()
+}
+
+fn options(names: &[&str], selected: &str) -> Html {
+    names
+        .iter()
+        .map(|name| html! {<option value={name.to_string()} selected={*name == selected}>{name}</option>})
+        .collect()
+}
+
 #[function_component(Menu)]
 pub fn menu() -> Html {
     let navigator = use_navigator().unwrap();
+    let rows = use_state(|| 10);
+    let cols = use_state(|| 10);
+    let rules = use_state(|| Rules::Classic);
     let players = use_state(|| 2);
+    // Players in each of two teams, `None` when everyone plays alone.
+    let team_size = use_state(|| None::<u8>);
+    let capture = use_state(|| false);
+    let seats = use_state(|| vec![HUMAN.to_string(); MAX_PLAYERS as usize]);
+    let player_count = team_size.map_or(*players, |size| size * 2);
     let onclick = {
-        let p = players.clone();
-        Callback::from(move |_| navigator.push(&Route::App { players: *p }))
+        let (rows, cols, rules) = (rows.clone(), cols.clone(), rules.clone());
+        let (seats, capture) = (seats.clone(), capture.clone());
+        let navigator = navigator.clone();
+        let team_size = *team_size;
+        Callback::from(move |_| {
+            let setup = Setup {
+                rows: *rows,
+                cols: *cols,
+                rules: *rules,
+                seats: seats[..player_count as usize].to_vec(),
+                teams: team_size.map(|_| Teams {
+                    count: 2,
+                    capture: *capture,
+                }),
+            };
+            navigator.push(&Route::App { setup })
+        })
+    };
+    let puzzles = {
+        let navigator = navigator.clone();
+        Callback::from(move |_| navigator.push(&Route::Puzzle { id: 0 }))
+    };
+    let resume = {
+        let navigator = navigator.clone();
+        Callback::from(move |_| navigator.push(&Route::Continue))
+    };
+    let recent = |index: usize| {
+        let navigator = navigator.clone();
+        Callback::from(move |_| navigator.push(&Route::Finished { index }))
+    };
+    let finished = saved::finished();
+    let recent_games = finished
+        .iter()
+        .enumerate()
+        .map(|(index, game)| html! {<button onclick={recent(index)}>{game.summary()}</button>})
+        .collect::<Html>();
+    let online = Callback::from(move |_| navigator.push(&Route::Online));
+    let number = |state: &UseStateHandle<usize>| {
+        let state = state.clone();
+        Callback::from(move |i: u8| state.set(i.into()))
+    };
+    let rows_changed = number(&rows);
+    let cols_changed = number(&cols);
+    let rules_changed = {
+        let rules = rules.clone();
+        Callback::from(move |event: Event| {
+            if let Some(selected) = Rules::from_name(&select_value(&event)) {
+                rules.set(selected);
+            }
+        })
     };
     let players_changed = {
         let players = players.clone();
         Callback::from(move |i: u8| players.set(i))
     };
+    let mode_changed = {
+        let team_size = team_size.clone();
+        Callback::from(move |event: Event| team_size.set(select_value(&event).parse().ok()))
+    };
+    let capture_changed = {
+        let capture = capture.clone();
+        Callback::from(move |_| capture.set(!*capture))
+    };
+    let seat_changed = |seat: usize| {
+        let seats = seats.clone();
+        Callback::from(move |event: Event| {
+            let mut changed = (*seats).clone();
+            changed[seat] = select_value(&event);
+            seats.set(changed);
+        })
+    };
+    let seat_names: Vec<&str> = [HUMAN].into_iter().chain(ai::NAMES).collect();
+    let size = (MIN_SIZE as u8, MAX_SIZE as u8);
     html! {
         <>
-            <style>{r#"
-            .menu {
-                padding: 1rem;
-                display: flex;
-                align-items: center;
-                flex-direction: column;
-            }
-            .menu * {
-                margin: 1rem;
-                padding: 0.5rem;
-            }
-            "#}</style>
+            <style>{MENU_STYLE}</style>
             <div class={classes!("menu")}>
-                <label for="players">{"Players: "}</label>
-                <Number max_value=7 min_value=2 inital_value={*players} update={players_changed} />
+                if saved::current().is_some() {
+                    <button onclick={resume}>{"Continue game"}</button>
+                }
+                <label>{"Rows: "}</label>
+                <Number min_value={size.0} max_value={size.1} inital_value={*rows as u8} update={rows_changed} />
+                <label>{"Columns: "}</label>
+                <Number min_value={size.0} max_value={size.1} inital_value={*cols as u8} update={cols_changed} />
+                <label>{"Rules: "}
+                    <select onchange={rules_changed}>{options(&Rules::NAMES, rules.name())}</select>
+                </label>
+                <label>{"Mode: "}
+                    <select onchange={mode_changed}>
+                        <option value="" selected={team_size.is_none()}>{"Free for all"}</option>
+                        {
+                            TEAM_SIZES.iter().map(|size| html! {
+                                <option value={size.to_string()} selected={*team_size == Some(*size)}>
+                                    {format!("{}v{}", size, size)}
+                                </option>
+                            }).collect::<Html>()
+                        }
+                    </select>
+                </label>
+                if team_size.is_some() {
+                    <label>
+                        <input type="checkbox" checked={*capture} onchange={capture_changed} />
+                        {"Teammates capture each other"}
+                    </label>
+                } else {
+                    <label for="players">{"Players: "}</label>
+                    <Number max_value={MAX_PLAYERS} min_value=2 inital_value={*players} update={players_changed} />
+                }
+                {
+                    (0..player_count as usize).map(|seat| html! {
+                        <label class={classes!(format!("player-{}", seat))}>
+                            {format!("Player {}: ", seat)}
+                            <select onchange={seat_changed(seat)}>{options(&seat_names, &seats[seat])}</select>
+                        </label>
+                    }).collect::<Html>()
+                }
                 <button {onclick}>{"Start Game"}</button>
+                <button onclick={puzzles}>{"Puzzles"}</button>
+                <button onclick={online}>{"Play Online"}</button>
+                if !finished.is_empty() {
+                    <label>{"Recent games: "}</label>
+                    {recent_games}
+                }
             </div>
         </>
     }
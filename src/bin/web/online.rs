@@ -0,0 +1,398 @@
+use chain_reaction::board::{CellState, Rules, MAX_PLAYERS};
+use chain_reaction::clock::{self, TimeoutAction};
+use chain_reaction::net::{cell_states, ClientMessage, GameState, ServerMessage};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlInputElement, MessageEvent, WebSocket};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::app::Route;
+use crate::board::GameBoard;
+use crate::menu::{Number, MENU_STYLE};
+use crate::setup::Setup;
+
+/// Room code used in route for creating new room.
+pub const NEW_ROOM: &str = "new";
+const ROWS: usize = 10;
+const COLS: usize = 10;
+
+/// Port of `server --websocket` when not given in menu.
+const DEFAULT_PORT: u16 = 7879;
+
+/// Game hosted by server, joined by name.
+#[derive(Clone, PartialEq)]
+pub struct Online {
+    pub server: String,
+    pub name: String,
+    /// Code of room to join, new room is created if not given.
+    pub room: Option<String>,
+    /// Whether games of room are only watched.
+    pub watch: bool,
+}
+
+impl Online {
+    /// Provides message joining or creating room.
+    pub fn join(&self, players: u8) -> ClientMessage {
+        let name = self.name.clone();
+        match self.room {
+            Some(ref room) if self.watch => ClientMessage::Watch {
+                room: Some(room.clone()),
+            },
+            Some(ref room) => ClientMessage::Join {
+                name,
+                room: Some(room.clone()),
+            },
+            None => ClientMessage::Create {
+                name,
+                rows: ROWS,
+                cols: COLS,
+                players,
+                rules: Rules::Classic,
+            },
+        }
+    }
+}
+
+/// WebSocket to game server, closed when dropped.
+pub struct Connection {
+    socket: WebSocket,
+    _onopen: Closure<dyn FnMut()>,
+    _onmessage: Closure<dyn FnMut(MessageEvent)>,
+    _onclose: Closure<dyn FnMut()>,
+}
+
+fn send(socket: &WebSocket, message: &ClientMessage) {
+    let text = serde_json::to_string(message).expect("messages are serializable");
+    // Lost connection is reported by onclose.
+    let _ = socket.send_with_str(&text);
+}
+
+impl Connection {
+    /// Connects to server, sending first message once connected.
+    pub fn open(
+        url: &str,
+        first: ClientMessage,
+        received: Callback<ServerMessage>,
+        closed: Callback<()>,
+    ) -> Result<Self, String> {
+        let socket = WebSocket::new(url).map_err(|_| format!("invalid server {}", url))?;
+        let onopen = {
+            let socket = socket.clone();
+            Closure::<dyn FnMut()>::new(move || send(&socket, &first))
+        };
+        let onmessage = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            let message = event
+                .data()
+                .as_string()
+                .and_then(|text| serde_json::from_str(&text).ok());
+            if let Some(message) = message {
+                received.emit(message);
+            }
+        });
+        let onclose = Closure::<dyn FnMut()>::new(move || closed.emit(()));
+        socket.set_onopen(Some(onopen.as_ref().unchecked_ref()));
+        socket.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+        socket.set_onclose(Some(onclose.as_ref().unchecked_ref()));
+        Ok(Self {
+            socket,
+            _onopen: onopen,
+            _onmessage: onmessage,
+            _onclose: onclose,
+        })
+    }
+
+    pub fn send(&self, message: &ClientMessage) {
+        send(&self.socket, message);
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.socket.set_onclose(None);
+        let _ = self.socket.close();
+    }
+}
+
+/// Game as pushed by server.
+#[derive(Default)]
+pub struct Remote {
+    pub code: Option<String>,
+    /// Names in lobby and players needed, till game starts.
+    pub lobby: Option<(Vec<String>, u8)>,
+    pub player: Option<u8>,
+    /// Secret for taking back seat after reconnecting.
+    token: Option<String>,
+    /// Whether connection to server is lost.
+    pub closed: bool,
+    pub state: Option<GameState>,
+    /// Explosion waves yet to be shown, oldest first.
+    pub waves: VecDeque<Vec<Vec<CellState>>>,
+    /// State after waves are shown.
+    pending: Option<GameState>,
+}
+
+impl Remote {
+    pub fn new(code: Option<String>) -> Self {
+        Self {
+            code,
+            ..Self::default()
+        }
+    }
+
+    /// Updates game with message, provides text for errors and notices.
+    pub fn receive(&mut self, message: ServerMessage) -> Option<String> {
+        let name = |state: &Option<GameState>, player: u8| match state {
+            Some(state) => state.names[player as usize].clone(),
+            None => format!("player {}", player),
+        };
+        match message {
+            ServerMessage::Room { code } => self.code = Some(code),
+            ServerMessage::Lobby { names, players } => self.lobby = Some((names, players)),
+            ServerMessage::Welcome { player, token } => {
+                self.player = Some(player);
+                self.token = Some(token);
+            }
+            ServerMessage::State(state) if self.waves.is_empty() => {
+                self.lobby = None;
+                self.state = Some(state);
+            }
+            ServerMessage::State(state) => self.pending = Some(state),
+            ServerMessage::Moved { .. } => {}
+            ServerMessage::Wave {
+                exploding, cells, ..
+            } => self.waves.push_back(cell_states(&cells, &exploding)),
+            ServerMessage::Left { player } => {
+                return Some(format!("{} lost connection", name(&self.state, player)))
+            }
+            ServerMessage::Returned { player } => {
+                return Some(format!("{} is back", name(&self.state, player)))
+            }
+            ServerMessage::Resigned { player } => {
+                return Some(format!("{} resigned", name(&self.state, player)))
+            }
+            ServerMessage::DrawOffered { player } => {
+                return Some(format!("{} offers a draw", name(&self.state, player)))
+            }
+            ServerMessage::DrawAccepted { player } => {
+                return Some(format!("{} accepts the draw", name(&self.state, player)))
+            }
+            ServerMessage::DrawDeclined { player } => {
+                return Some(format!("{} declines the draw", name(&self.state, player)))
+            }
+            ServerMessage::Forfeited { player } => {
+                return Some(format!(
+                    "{} did not return and is out",
+                    name(&self.state, player)
+                ))
+            }
+            ServerMessage::TimedOut { player, action } => {
+                let outcome = match action {
+                    TimeoutAction::Forfeit => "is out",
+                    TimeoutAction::RandomMove => "gets random move",
+                    TimeoutAction::Skip => "loses the turn",
+                };
+                return Some(format!(
+                    "{} ran out of time and {}",
+                    name(&self.state, player),
+                    outcome
+                ));
+            }
+            ServerMessage::Error { message } => return Some(message),
+        }
+        None
+    }
+
+    /// Provides message taking back seat, if game of player is running.
+    pub fn rejoin(&self) -> Option<ClientMessage> {
+        let running = self.state.as_ref().is_some_and(|state| !state.is_over());
+        let token = self.token.clone().filter(|_| running)?;
+        Some(ClientMessage::Rejoin { token })
+    }
+
+    /// Whether client plays in game not yet over.
+    pub fn playing(&self) -> bool {
+        match (&self.state, self.player) {
+            (Some(state), Some(player)) => !state.is_over() && state.alive[player as usize],
+            _ => false,
+        }
+    }
+
+    /// Counts down time of player to move, till server sends next state.
+    pub fn tick(&mut self, ms: u64) {
+        let Some(ref mut state) = self.state else {
+            return;
+        };
+        if state.is_over() {
+            return;
+        }
+        if let Some(ref mut left) = state.time_left {
+            let left = &mut left[state.turn as usize];
+            *left = left.saturating_sub(ms);
+        }
+    }
+
+    /// Shows next explosion wave, or state once waves are over.
+    pub fn next_wave(&mut self) {
+        self.waves.pop_front();
+        if self.waves.is_empty() {
+            if let Some(state) = self.pending.take() {
+                self.state = Some(state);
+            }
+        }
+    }
+
+    /// Provides cells to be shown.
+    pub fn cells(&self) -> Vec<Vec<CellState>> {
+        match (self.waves.front(), &self.state) {
+            (Some(wave), _) => wave.clone(),
+            (None, Some(state)) => cell_states(&state.cells, &[]),
+            (None, None) => vec![],
+        }
+    }
+
+    /// Provides heading and player it is about.
+    pub fn heading(&self) -> (String, u8) {
+        let you = |player| {
+            if self.player == Some(player) {
+                " (you)"
+            } else {
+                ""
+            }
+        };
+        match (&self.state, &self.lobby) {
+            (_, Some((names, players))) => {
+                let room = self.code.as_deref().unwrap_or("");
+                let heading = format!(
+                    "Room {}: waiting for players ({}/{}) {}",
+                    room,
+                    names.len(),
+                    players,
+                    names.join(", ")
+                );
+                (heading, 0)
+            }
+            (Some(state), None) => match state.winner {
+                Some(winner) => (
+                    format!("Winner: {}{}", state.names[winner as usize], you(winner)),
+                    winner,
+                ),
+                None if state.drawn => ("Draw".to_string(), state.turn),
+                None => {
+                    let left = match state.time_left {
+                        Some(ref left) => format!(
+                            " \u{23F1}{}",
+                            clock::minutes(Duration::from_millis(left[state.turn as usize]))
+                        ),
+                        None => String::new(),
+                    };
+                    (
+                        format!(
+                            "Current Player: {}{}{}",
+                            state.names[state.turn as usize],
+                            you(state.turn),
+                            left
+                        ),
+                        state.turn,
+                    )
+                }
+            },
+            (None, None) => ("Connecting...".to_string(), 0),
+        }
+    }
+}
+
+/// Details of room kept in query of route.
+#[derive(Serialize, Deserialize, Default)]
+pub struct RoomQuery {
+    pub server: String,
+    pub name: String,
+    pub players: u8,
+    #[serde(default)]
+    pub watch: bool,
+}
+
+fn default_server() -> String {
+    let host = web_sys::window()
+        .and_then(|window| window.location().hostname().ok())
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "localhost".to_string());
+    format!("ws://{}:{}", host, DEFAULT_PORT)
+}
+
+#[function_component(OnlineMenu)]
+pub fn online_menu() -> Html {
+    let navigator = use_navigator().unwrap();
+    let players = use_state(|| 2);
+    let name = use_node_ref();
+    let server = use_node_ref();
+    let code = use_node_ref();
+    let value = |node: &NodeRef| {
+        node.cast::<HtmlInputElement>()
+            .map(|input| input.value().trim().to_string())
+            .unwrap_or_default()
+    };
+    let go = {
+        let (name, server, code) = (name.clone(), server.clone(), code.clone());
+        let players = players.clone();
+        move |create: bool, watch: bool| {
+            let (name, server, code) = (name.clone(), server.clone(), code.clone());
+            let (navigator, players) = (navigator.clone(), players.clone());
+            Callback::from(move |_| {
+                let code = if create {
+                    NEW_ROOM.to_string()
+                } else {
+                    value(&code).to_uppercase()
+                };
+                let query = RoomQuery {
+                    server: value(&server),
+                    name: value(&name),
+                    players: *players,
+                    watch,
+                };
+                let _ = navigator.push_with_query(&Route::Room { code }, &query);
+            })
+        }
+    };
+    let players_changed = {
+        let players = players.clone();
+        Callback::from(move |i: u8| players.set(i))
+    };
+    html! {
+        <>
+        <style>{MENU_STYLE}</style>
+        <div class={classes!("menu")}>
+            <label>{"Name: "}<input ref={name} type="text" /></label>
+            <label>{"Server: "}<input ref={server} type="text" value={default_server()} /></label>
+            <label>{"Players: "}</label>
+            <Number max_value={MAX_PLAYERS} min_value=2 inital_value={*players} update={players_changed} />
+            <button onclick={go(true, false)}>{"Create Room"}</button>
+            <label>{"Room code: "}<input ref={code} type="text" /></label>
+            <button onclick={go(false, false)}>{"Join Room"}</button>
+            <button onclick={go(false, true)}>{"Watch Room"}</button>
+        </div>
+        </>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RoomProps {
+    pub code: String,
+}
+
+#[function_component(Room)]
+pub fn room(RoomProps { code }: &RoomProps) -> Html {
+    let location = use_location().unwrap();
+    let query: RoomQuery = location.query().unwrap_or_default();
+    let online = Online {
+        server: query.server,
+        name: query.name,
+        room: (code != NEW_ROOM).then(|| code.clone()),
+        watch: query.watch,
+    };
+    let setup = Setup::new(query.players.max(2));
+    html! {<GameBoard {setup} online={Some(online)} />}
+}
@@ -0,0 +1,158 @@
+use chain_reaction::board::{Board, BoardState};
+use chain_reaction::puzzle::{parse, Puzzle};
+use gloo_timers::callback::Timeout;
+use std::cell::RefCell;
+use std::rc::Rc;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::app::Route;
+use crate::board::{cells_table, player_colors, BOARD_STYLE};
+
+const PUZZLES: &str = include_str!("../../../puzzles/basic.txt");
+
+pub enum PuzzleBoardAction {
+    MoveAnimation,
+    Move(usize, usize),
+    Reset(usize),
+}
+
+pub struct PuzzleBoardState {
+    puzzles: Vec<Puzzle>,
+    id: RefCell<usize>,
+    board: RefCell<Board>,
+    message: RefCell<String>,
+    attempted: RefCell<bool>,
+}
+
+impl PuzzleBoardState {
+    fn new(id: usize) -> Self {
+        let puzzles = parse(PUZZLES).expect("built-in puzzles are valid");
+        let id = id.min(puzzles.len() - 1);
+        let board = puzzles[id].board().clone();
+        Self {
+            puzzles,
+            id: RefCell::new(id),
+            board: RefCell::new(board),
+            message: RefCell::new(String::new()),
+            attempted: RefCell::new(false),
+        }
+    }
+}
+
+impl Reducible for PuzzleBoardState {
+    type Action = PuzzleBoardAction;
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        {
+            let mut board = self.board.borrow_mut();
+            let puzzle = &self.puzzles[*self.id.borrow()];
+            match action {
+                PuzzleBoardAction::MoveAnimation => if !board.next_iteration() {},
+                PuzzleBoardAction::Move(r, c) if !*self.attempted.borrow() => {
+                    let cur_player = board.current_player_id();
+                    *self.message.borrow_mut() = match puzzle.check(r, c) {
+                        Ok(solved) => {
+                            *self.attempted.borrow_mut() = true;
+                            let _ = board.player_move(cur_player, r, c);
+                            if solved {
+                                "Solved!".to_string()
+                            } else {
+                                "Goal not achieved, try again.".to_string()
+                            }
+                        }
+                        Err(msg) => format!("{:?}", msg),
+                    };
+                }
+                PuzzleBoardAction::Move(..) => {}
+                PuzzleBoardAction::Reset(id) => {
+                    let id = id.min(self.puzzles.len() - 1);
+                    *self.id.borrow_mut() = id;
+                    *board = self.puzzles[id].board().clone();
+                    self.message.borrow_mut().drain(..);
+                    *self.attempted.borrow_mut() = false;
+                }
+            }
+        }
+        self
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PuzzleBoardProps {
+    pub id: usize,
+}
+
+#[function_component(PuzzleBoard)]
+pub fn puzzle_board(PuzzleBoardProps { id }: &PuzzleBoardProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let puzzle_state = use_reducer(|| PuzzleBoardState::new(*id));
+    {
+        let p = puzzle_state.clone();
+        use_effect_with_deps(
+            move |id| {
+                p.dispatch(PuzzleBoardAction::Reset(*id));
+            },
+            *id,
+        )
+    }
+    let total = puzzle_state.puzzles.len();
+    let id = *puzzle_state.id.borrow();
+    let back_to_menu = {
+        let navigator = navigator.clone();
+        Callback::from(move |_| navigator.push(&Route::Menu))
+    };
+    let previous = {
+        let navigator = navigator.clone();
+        Callback::from(move |_| {
+            navigator.push(&Route::Puzzle {
+                id: id.saturating_sub(1),
+            })
+        })
+    };
+    let next = Callback::from(move |_| {
+        navigator.push(&Route::Puzzle {
+            id: (id + 1).min(total - 1),
+        })
+    });
+    let reset = {
+        let p = puzzle_state.clone();
+        Callback::from(move |_| p.dispatch(PuzzleBoardAction::Reset(id)))
+    };
+    let onclick = {
+        let p = puzzle_state.clone();
+        Callback::from(move |(r, c): (usize, usize)| {
+            p.dispatch(PuzzleBoardAction::Move(r, c));
+        })
+    };
+
+    let puzzle = &puzzle_state.puzzles[id];
+    let (cur_player, cells, message) = {
+        let board = puzzle_state.board.borrow();
+        if !matches!(board.state(), BoardState::Wait | BoardState::GameOver(_)) {
+            let p = puzzle_state.clone();
+            Timeout::new(1_000, move || p.dispatch(PuzzleBoardAction::MoveAnimation)).forget();
+        }
+        (
+            puzzle.board().current_player_id(),
+            board.cells(),
+            puzzle_state.message.borrow().clone(),
+        )
+    };
+    html! {
+        <>
+        <style>{player_colors(puzzle.board().player_count())}{BOARD_STYLE}</style>
+        <div class={classes!("app")}>
+            <h1>{ format!("Puzzle {}/{}: {}", id + 1, total, puzzle.name()) }</h1>
+            <h2 class={classes!(format!("player-{}", cur_player))}>
+            {format!("Player {} to {}", cur_player, puzzle.goal())}{"  "}
+            <button onclick={back_to_menu}>{"\u{1F519}"}</button>
+            <button onclick={previous} disabled={id == 0}>{"\u{23EE}"}</button>
+            <button onclick={reset}>{"\u{1F504}"}</button>
+            <button onclick={next} disabled={id + 1 == total}>{"\u{23ED}"}</button>
+            </h2>
+            <p style="color: darkred;">{message}<br/></p>
+            {cells_table(&cells, &onclick)}
+        </div>
+        </>
+    }
+}
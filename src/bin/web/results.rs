@@ -0,0 +1,161 @@
+use chain_reaction::board::Standing;
+use chain_reaction::stats::GameStats;
+use yew::prelude::*;
+
+const CHART_WIDTH: usize = 300;
+const CHART_HEIGHT: usize = 150;
+
+pub const RESULTS_STYLE: &str = r#"
+        .results {
+            position: fixed;
+            inset: 0;
+            display: flex;
+            align-items: center;
+            justify-content: center;
+            background-color: rgba(0, 0, 0, 0.5);
+        }
+        .results > div {
+            background-color: white;
+            padding: 1rem 2rem;
+            border-radius: 0.5rem;
+            text-align: center;
+        }
+        .results table {margin: auto; border-collapse: collapse;}
+        .results td, .results th {padding: 0.2rem 0.6rem;}
+        .results svg {width: 20rem; height: 10rem; border: 1px solid LightGray;}
+        .results button {margin: 0.5rem; padding: 0.5rem;}
+        "#;
+
+/// Line of cells owned by player over turns, as points of svg polyline.
+fn points(timeline: &[Vec<usize>], player: usize, cells: usize) -> String {
+    let turns = timeline.len().max(2) - 1;
+    timeline
+        .iter()
+        .enumerate()
+        .map(|(turn, territory)| {
+            format!(
+                "{},{}",
+                turn * CHART_WIDTH / turns,
+                CHART_HEIGHT - territory[player] * CHART_HEIGHT / cells.max(1)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Chart of cells owned by each player after each turn.
+fn territory_chart(stats: &GameStats, cells: usize) -> Html {
+    let timeline = stats.timeline();
+    html! {
+        <svg viewBox={format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT)} preserveAspectRatio="none">
+        {
+            (0..stats.players().len()).map(|player| html! {
+                <polyline
+                    class={classes!(format!("player-{}", player))}
+                    points={points(timeline, player, cells)}
+                    fill="none"
+                    stroke="currentColor"
+                    stroke-width="2"
+                />
+            }).collect::<Html>()
+        }
+        </svg>
+    }
+}
+
+fn stats_table(stats: &GameStats, names: &[String]) -> Html {
+    html! {
+        <table>
+            <tr>
+                <th>{"Player"}</th>
+                <th>{"Atoms"}</th>
+                <th>{"Explosions"}</th>
+                <th>{"Captured"}</th>
+                <th>{"Longest chain"}</th>
+                <th>{"Peak territory"}</th>
+            </tr>
+            {
+                stats.players().iter().zip(names).enumerate().map(|(player, (stats, name))| html! {
+                    <tr class={classes!(format!("player-{}", player))}>
+                        <td>{name}</td>
+                        <td>{stats.atoms_placed}</td>
+                        <td>{stats.explosions}</td>
+                        <td>{stats.cells_captured}</td>
+                        <td>{format!(
+                            "{} waves, {} cells",
+                            stats.longest_chain.waves,
+                            stats.longest_chain.cells
+                        )}</td>
+                        <td>{stats.peak_territory}</td>
+                    </tr>
+                }).collect::<Html>()
+            }
+        </table>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ResultsProps {
+    pub standings: Vec<Standing>,
+    pub names: Vec<String>,
+    /// Stats of game with cells of board, not known for online games.
+    #[prop_or_default]
+    pub stats: Option<(GameStats, usize)>,
+    #[prop_or_default]
+    pub rematch: Option<Callback<MouseEvent>>,
+    #[prop_or_default]
+    pub replay: Option<Callback<MouseEvent>>,
+    pub menu: Callback<MouseEvent>,
+}
+
+#[function_component(Results)]
+pub fn results(
+    ResultsProps {
+        standings,
+        names,
+        stats,
+        rematch,
+        replay,
+        menu,
+    }: &ResultsProps,
+) -> Html {
+    html! {
+        <>
+        <style>{RESULTS_STYLE}</style>
+        <div class={classes!("results")}>
+            <div>
+                <h2>{"Results"}</h2>
+                <table>
+                {
+                    standings.iter().map(|standing| html! {
+                        <tr class={classes!(format!("player-{}", standing.player))}>
+                            <td>{format!("{}.", standing.place)}</td>
+                            <td>{&names[standing.player as usize]}</td>
+                            <td>{match standing.eliminated {
+                                Some(turn) => format!("out on turn {}", turn),
+                                None => String::new(),
+                            }}</td>
+                        </tr>
+                    }).collect::<Html>()
+                }
+                </table>
+                if let Some((stats, cells)) = stats {
+                    <h3>{"Statistics"}</h3>
+                    {stats_table(stats, names)}
+                    <h3>{"Territory"}</h3>
+                    {territory_chart(stats, *cells)}
+                }
+                <div>
+                    if let Some(rematch) = rematch {
+                        <button onclick={rematch}>{"Rematch"}</button>
+                    }
+                    if let Some(replay) = replay {
+                        <button onclick={replay}>{"Replay"}</button>
+                    }
+                    <button onclick={menu}>{"Menu"}</button>
+                </div>
+            </div>
+        </div>
+        </>
+    }
+}
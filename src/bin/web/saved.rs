@@ -0,0 +1,115 @@
+use chain_reaction::board::BoardState;
+use chain_reaction::record::GameRecord;
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+use crate::computer::position;
+use crate::setup::{Setup, HUMAN};
+
+/// Key of game being played.
+const CURRENT_KEY: &str = "chain_reaction.current";
+/// Key of recent finished games, latest first.
+const FINISHED_KEY: &str = "chain_reaction.finished";
+/// Finished games kept for replay.
+const RECENT_GAMES: usize = 5;
+
+/// Local game kept in browser, so it survives reloading the page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedGame {
+    /// Player of each seat, [`HUMAN`] or name of computer player.
+    pub seats: Vec<String>,
+    /// Game record as text.
+    pub record: String,
+}
+
+impl SavedGame {
+    pub fn record(&self) -> Option<GameRecord> {
+        GameRecord::parse(&self.record).ok()
+    }
+
+    /// Provides setup game was started with.
+    pub fn setup(&self) -> Option<Setup> {
+        let record = self.record()?;
+        Some(Setup {
+            rows: record.rows,
+            cols: record.cols,
+            rules: record.rules,
+            seats: self.seats.clone(),
+            teams: record.teams,
+        })
+    }
+
+    /// Describes game for menu.
+    pub fn summary(&self) -> String {
+        let Some(record) = self.record() else {
+            return "Unknown game".to_string();
+        };
+        let winner = match position(&record) {
+            Some((board, _)) => match *board.state() {
+                BoardState::GameOver(winner) => match self.seats.get(winner as usize) {
+                    Some(seat) if seat != HUMAN => format!("{} ({}) won", winner, seat),
+                    _ => format!("{} won", winner),
+                },
+                BoardState::Draw => "Draw".to_string(),
+                _ => "Not over".to_string(),
+            },
+            None => "Invalid game".to_string(),
+        };
+        format!(
+            "{}, {}x{} {}, {} moves",
+            winner,
+            record.rows,
+            record.cols,
+            record.rules.name(),
+            record.moves.len()
+        )
+    }
+}
+
+/// Provides game left unfinished.
+pub fn current() -> Option<SavedGame> {
+    LocalStorage::get(CURRENT_KEY).ok()
+}
+
+/// Provides recent finished games, latest first.
+pub fn finished() -> Vec<SavedGame> {
+    LocalStorage::get(FINISHED_KEY).unwrap_or_default()
+}
+
+/// Keeps game being played, or moves it to recent games once over.
+pub fn store(game: SavedGame, over: bool) {
+    // Storage may be full or disabled, games are then just not kept.
+    if over {
+        LocalStorage::delete(CURRENT_KEY);
+        let mut games = finished();
+        games.insert(0, game);
+        games.truncate(RECENT_GAMES);
+        let _ = LocalStorage::set(FINISHED_KEY, games);
+    } else {
+        let _ = LocalStorage::set(CURRENT_KEY, game);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_game_restores_position() {
+        let setup = Setup::new(3);
+        let mut record = setup.record();
+        record.moves = vec![(0, 0, 0), (1, 1, 1), (0, 2, 2)];
+        record.resigned = vec![(2, 2)];
+        let game = SavedGame {
+            seats: setup.seats.clone(),
+            record: record.to_string(),
+        };
+        assert_eq!(game.setup(), Some(setup));
+        let (board, stats) = position(&game.record().unwrap()).unwrap();
+        // Player 2 resigned before their first move, so player 0 moved again.
+        assert_eq!(board.current_player_id(), 1);
+        assert!(!board.is_player_alive(2));
+        assert_eq!(stats.players()[0].atoms_placed, 2);
+        assert_eq!(game.summary(), "Not over, 10x10 classic, 3 moves");
+    }
+}
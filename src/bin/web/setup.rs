@@ -0,0 +1,156 @@
+use chain_reaction::ai;
+use chain_reaction::board::{Rules, Teams, MAX_PLAYERS, MAX_SIZE, MIN_SIZE};
+use chain_reaction::record::GameRecord;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Seat played by person at the browser.
+pub const HUMAN: &str = "human";
+
+/// Local game as chosen in menu, kept in route so links reproduce it.
+///
+/// Written as `<rows>x<cols>-<rules>-<seat>,<seat>...` followed by
+/// `-teams<count>` and `-capture` for team games, e.g.
+/// `8x8-wrap-human,minimax,human,greedy-teams2`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Setup {
+    pub rows: usize,
+    pub cols: usize,
+    pub rules: Rules,
+    /// Player of each seat, [`HUMAN`] or name of computer player.
+    pub seats: Vec<String>,
+    pub teams: Option<Teams>,
+}
+
+impl Setup {
+    /// Provides classic 10x10 game of human players.
+    pub fn new(players: u8) -> Self {
+        Self {
+            rows: 10,
+            cols: 10,
+            rules: Rules::Classic,
+            seats: vec![HUMAN.to_string(); players as usize],
+            teams: None,
+        }
+    }
+
+    pub fn players(&self) -> u8 {
+        self.seats.len() as u8
+    }
+
+    /// Provides record of game without moves.
+    pub fn record(&self) -> GameRecord {
+        let mut record = GameRecord::new(self.rows, self.cols, self.players(), self.rules);
+        record.teams = self.teams;
+        record
+    }
+}
+
+impl Display for Setup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}x{}-{}-{}",
+            self.rows,
+            self.cols,
+            self.rules.name(),
+            self.seats.join(",")
+        )?;
+        if let Some(teams) = self.teams {
+            write!(f, "-teams{}", teams.count)?;
+            if teams.capture {
+                write!(f, "-capture")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Setup {
+    type Err = String;
+
+    fn from_str(setup: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid setup {}", setup);
+        let mut parts = setup.split('-');
+        let (rows, cols) = parts
+            .next()
+            .and_then(|size| size.split_once('x'))
+            .ok_or_else(invalid)?;
+        let (rows, cols): (usize, usize) = (
+            rows.parse().map_err(|_| invalid())?,
+            cols.parse().map_err(|_| invalid())?,
+        );
+        let rules = parts
+            .next()
+            .and_then(Rules::from_name)
+            .ok_or_else(invalid)?;
+        let seats: Vec<String> = parts
+            .next()
+            .ok_or_else(invalid)?
+            .split(',')
+            .map(str::to_string)
+            .collect();
+        let teams = match parts.next() {
+            Some(teams) => {
+                let count = teams.strip_prefix("teams").ok_or_else(invalid)?;
+                let count = count.parse().map_err(|_| invalid())?;
+                let capture = match parts.next() {
+                    Some("capture") => true,
+                    Some(_) => return Err(invalid()),
+                    None => false,
+                };
+                Some(Teams { count, capture })
+            }
+            None => None,
+        };
+        let sizes = MIN_SIZE..=MAX_SIZE;
+        let known = |seat: &String| seat == HUMAN || ai::NAMES.contains(&seat.as_str());
+        let even =
+            |teams: Teams| teams.count >= 2 && seats.len().is_multiple_of(teams.count as usize);
+        if parts.next().is_some()
+            || !sizes.contains(&rows)
+            || !sizes.contains(&cols)
+            || !(2..=MAX_PLAYERS as usize).contains(&seats.len())
+            || !seats.iter().all(known)
+            || !teams.is_none_or(even)
+        {
+            return Err(invalid());
+        }
+        Ok(Self {
+            rows,
+            cols,
+            rules,
+            seats,
+            teams,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn setup_in_route() {
+        let mut setup = Setup::new(4);
+        setup.rules = Rules::Wrap;
+        setup.seats[1] = "minimax".to_string();
+        setup.teams = Some(Teams {
+            count: 2,
+            capture: true,
+        });
+        let text = setup.to_string();
+        assert_eq!(text, "10x10-wrap-human,minimax,human,human-teams2-capture");
+        assert_eq!(text.parse(), Ok(setup));
+        for invalid in [
+            "2x10-classic-human,human",
+            "10x10-hex-human,human",
+            "10x10-classic-human",
+            "10x10-classic-human,perfect",
+            "10x10-classic-human,human,human-teams2",
+            "10x10-classic-human,human-teams2-always",
+        ] {
+            assert!(invalid.parse::<Setup>().is_err(), "{}", invalid);
+        }
+    }
+}
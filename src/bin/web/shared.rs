@@ -0,0 +1,83 @@
+use chain_reaction::board::BoardState;
+use chain_reaction::record::GameRecord;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::app::Route;
+use crate::board::{cells_table, player_colors, BOARD_STYLE};
+
+#[derive(Properties, PartialEq)]
+pub struct SharedGameProps {
+    /// Game in compact notation.
+    pub game: String,
+}
+
+/// Game opened from link, shown after its last move and stepped through
+/// move by move.
+#[function_component(SharedGame)]
+pub fn shared_game(SharedGameProps { game }: &SharedGameProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let back_to_menu = Callback::from(move |_| navigator.push(&Route::Menu));
+    // Moves shown, `None` for all of them.
+    let shown = use_state(|| None::<usize>);
+    {
+        let shown = shown.clone();
+        use_effect_with_deps(move |_| shown.set(None), game.clone())
+    }
+    let record = match GameRecord::from_notation(game) {
+        Ok(record) => record,
+        Err(msg) => {
+            return html! {
+                <>
+                <style>{BOARD_STYLE}</style>
+                <div class={classes!("app")}>
+                    <h1>{ "Chain Reaction" }</h1>
+                    <h2>{"Game cannot be opened  "}<button onclick={back_to_menu}>{"\u{1F519}"}</button></h2>
+                    <p style="color: darkred;">{format!("{:?}", msg)}</p>
+                </div>
+                </>
+            };
+        }
+    };
+    let total = record.moves.len();
+    let moves = shown.unwrap_or(total).min(total);
+    let board = record
+        .replay_moves(moves)
+        .expect("moves were played reading notation");
+    let step = |to: usize| {
+        let shown = shown.clone();
+        Callback::from(move |_| shown.set(Some(to)))
+    };
+    let mut position = record.clone();
+    position.truncate(moves);
+    let cur_player = board.current_player_id();
+    let heading = match board.state() {
+        BoardState::GameOver(winner) => format!("Winner: {}", winner),
+        BoardState::Draw => "Draw".to_string(),
+        _ => format!("Current Player: {}", cur_player),
+    };
+    let onclick = Callback::from(|_: (usize, usize)| {});
+    html! {
+        <>
+        <style>{player_colors(record.players)}{BOARD_STYLE}</style>
+        <div class={classes!("app")}>
+            <h1>{ "Chain Reaction" }</h1>
+            <h2 class={classes!(format!("player-{}", cur_player))}>
+            {heading}{"  "}
+            <button onclick={back_to_menu}>{"\u{1F519}"}</button>
+            <button onclick={step(0)} disabled={moves == 0}>{"\u{23EE}"}</button>
+            <button onclick={step(moves.saturating_sub(1))} disabled={moves == 0}>{"\u{25C0}"}</button>
+            <button onclick={step(moves + 1)} disabled={moves == total}>{"\u{25B6}"}</button>
+            <button onclick={step(total)} disabled={moves == total}>{"\u{23ED}"}</button>
+            if moves < total {
+                <Link<Route> to={Route::Game { game: position.to_notation() }}>
+                    {"\u{1F517}"}
+                </Link<Route>>
+            }
+            </h2>
+            <p>{format!("Move {}/{}", moves, total)}<br/></p>
+            {cells_table(&board.cells(), &onclick)}
+        </div>
+        </>
+    }
+}
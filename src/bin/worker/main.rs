@@ -0,0 +1,9 @@
+#[path = "../web/computer.rs"]
+mod computer;
+
+use computer::Computer;
+use gloo_worker::Registrable;
+
+fn main() {
+    Computer::registrar().register();
+}
@@ -1,8 +1,16 @@
 //! Provides board for playing chain reaction.
 
 use crate::cell::Cell;
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
+/// Most players offered a game, beyond this their colours are hard to tell apart.
+pub const MAX_PLAYERS: u8 = 8;
+/// Fewest rows or columns of board offered a game.
+pub const MIN_SIZE: usize = 3;
+/// Most rows or columns of board offered a game, larger boards do not fit screens.
+pub const MAX_SIZE: usize = 20;
+
 /// Move Errors.
 #[derive(Debug, PartialEq, Eq)]
 pub enum MoveError {
@@ -16,10 +24,33 @@ pub enum MoveError {
     MoveNotComplete,
     /// Game over.
     GameOver,
+    /// Player is no longer in the game.
+    PlayerOut,
+    /// Player has no time left for the move.
+    OutOfTime,
+    /// Draw is answered while none was offered.
+    NoDrawOffer,
+}
+
+/// Position Errors.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PositionError {
+    /// Board has less than 3 rows or columns.
+    BoardTooSmall,
+    /// Rows do not have same number of columns.
+    UnevenRows,
+    /// Board has less than 2 players.
+    TooFewPlayers,
+    /// Current player is not part of the game.
+    UnknownPlayer,
+    /// Cell is owned by player not part of the game. The cell location is stored.
+    UnknownOwner(usize, usize),
+    /// Cell has no atoms or reached critical mass. The cell location is stored.
+    InvalidAtoms(usize, usize),
 }
 
 /// Board can be in any one of the states
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BoardState {
     /// When board is waiting for player input
     Wait,
@@ -29,6 +60,8 @@ pub enum BoardState {
     CheckWinCondition,
     /// When board has winner. The Player id is stored.
     GameOver(u8),
+    /// When players in the game agreed to end it without winner.
+    Draw,
 }
 
 /// Cell State
@@ -42,16 +75,162 @@ pub enum CellState {
     Empty,
 }
 
+/// Rules deciding neighbours of cells.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Rules {
+    /// Cells on edges and corners have fewer neighbours and lower critical mass.
+    #[default]
+    Classic,
+    /// Edges wrap around to the opposite side, every cell has 4 neighbours.
+    Wrap,
+}
+
+impl Rules {
+    /// Names of all rules.
+    pub const NAMES: [&'static str; 2] = ["classic", "wrap"];
+
+    /// Provides rules by name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::Rules;
+    /// assert_eq!(Rules::from_name("wrap"), Some(Rules::Wrap));
+    /// assert_eq!(Rules::from_name("hex"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "classic" => Some(Self::Classic),
+            "wrap" => Some(Self::Wrap),
+            _ => None,
+        }
+    }
+
+    /// Provides name of rules.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Classic => "classic",
+            Self::Wrap => "wrap",
+        }
+    }
+
+    fn critical_mass(self, row: usize, col: usize, rows: usize, cols: usize) -> u8 {
+        match self {
+            Self::Classic => Cell::critical_mass(row, col, rows, cols),
+            Self::Wrap => 4,
+        }
+    }
+
+    fn neighbors(self, row: usize, col: usize, rows: usize, cols: usize) -> Vec<(usize, usize)> {
+        match self {
+            Self::Classic => Cell::get_neighbors(row, col, rows, cols),
+            Self::Wrap => Cell::get_wrapped_neighbors(row, col, rows, cols),
+        }
+    }
+}
+
+/// Place of player in ranking of game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Standing {
+    /// Player id.
+    pub player: u8,
+    /// Place from 1, players still in the game or eliminated by the same
+    /// move share place.
+    pub place: u8,
+    /// Turn player was eliminated in, `None` while in the game.
+    pub eliminated: Option<usize>,
+}
+
+/// What happens to atoms of player leaving the game.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LeavePolicy {
+    /// Cells of player are emptied.
+    #[default]
+    Clear,
+    /// Atoms stay as neutral obstacles, other players capture them by explosions.
+    Neutral,
+}
+
+impl LeavePolicy {
+    /// Names of all leave policies.
+    pub const NAMES: [&'static str; 2] = ["clear", "neutral"];
+
+    /// Provides leave policy by name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::LeavePolicy;
+    /// assert_eq!(LeavePolicy::from_name("neutral"), Some(LeavePolicy::Neutral));
+    /// assert_eq!(LeavePolicy::from_name("keep"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "clear" => Some(Self::Clear),
+            "neutral" => Some(Self::Neutral),
+            _ => None,
+        }
+    }
+
+    /// Provides name of leave policy.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Clear => "clear",
+            Self::Neutral => "neutral",
+        }
+    }
+}
+
+/// Grouping of players into teams.
+///
+/// Seats alternate between teams, player `p` plays for team `p % count`.
+/// Teams take turns in order and players of a team take turns of their
+/// team in order, so turns keep alternating between teams even when
+/// teams lose players.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Teams {
+    /// Number of teams.
+    pub count: u8,
+    /// Whether explosions capture cells of teammates.
+    #[serde(default)]
+    pub capture: bool,
+}
+
+impl Teams {
+    /// Provides teams where teammates do not capture each other.
+    pub fn new(count: u8) -> Self {
+        Self {
+            count,
+            capture: false,
+        }
+    }
+}
+
 type BoxBoxCell = Box<[Box<[Cell]>]>;
 /// Board structure of game.
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct Board {
     cells: BoxBoxCell,
     rows: usize,
     cols: usize,
     cur_player: u8,
     players: Box<[bool]>,
+    /// Players who resigned or forfeited, out even while owning cells.
+    left: Box<[bool]>,
+    /// Players agreeing to a draw, until someone declines.
+    draw_offers: Box<[bool]>,
+    leave_policy: LeavePolicy,
+    teams: Option<Teams>,
+    /// Player of each team who moved last for the team.
+    last_moved: Box<[u8]>,
+    /// Turns taken so far.
+    turns: usize,
+    /// Players going out together with turn they went out in, in order.
+    eliminated: Vec<(Vec<u8>, usize)>,
     state: BoardState,
+    rules: Rules,
 }
 
 impl Board {
@@ -114,6 +293,240 @@ impl Board {
         &self.state
     }
 
+    /// Provides whether game is over, won by a player or drawn.
+    pub fn is_over(&self) -> bool {
+        matches!(self.state, BoardState::GameOver(_) | BoardState::Draw)
+    }
+
+    /// Provides number of rows in board.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Provides number of columns in board.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Provides number of players the game started with.
+    pub fn player_count(&self) -> u8 {
+        self.players.len() as u8
+    }
+
+    /// Provides teams of board, `None` when everyone plays for themselves.
+    pub fn teams(&self) -> Option<Teams> {
+        self.teams
+    }
+
+    /// Provides team of player, which is the player itself without teams.
+    pub fn team(&self, player: u8) -> u8 {
+        match self.teams {
+            Some(teams) => player % teams.count,
+            None => player,
+        }
+    }
+
+    /// Provides whether player is still in the game.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::Board;
+    /// let mut board = Board::new(3, 3, 2);
+    /// for (player, row, col) in [(0, 0, 0), (1, 1, 0), (0, 0, 0)] {
+    ///     board.player_move(player, row, col).unwrap();
+    ///     board.settle();
+    /// }
+    /// assert!(board.is_player_alive(0));
+    /// assert!(!board.is_player_alive(1));
+    /// ```
+    pub fn is_player_alive(&self, player: u8) -> bool {
+        let player = player as usize;
+        self.players.get(player).copied().unwrap_or(false) && !self.left[player]
+    }
+
+    /// Provides atoms at which cell explodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::Board;
+    /// let board = Board::new(4, 4, 2);
+    /// assert_eq!(board.critical_mass(0, 0), 2);
+    /// assert_eq!(board.critical_mass(0, 1), 3);
+    /// assert_eq!(board.critical_mass(1, 1), 4);
+    /// ```
+    pub fn critical_mass(&self, row: usize, col: usize) -> u8 {
+        self.rules.critical_mass(row, col, self.rows, self.cols)
+    }
+
+    /// Provides rules of board.
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    /// Provides number of turns taken, moves and skipped turns.
+    pub fn turns(&self) -> usize {
+        self.turns
+    }
+
+    /// Provides players out of the game with turn they went out in, in
+    /// order of elimination.
+    ///
+    /// Turn of player eliminated by move is the number of that move,
+    /// counting from 1. Players who resign go out after turns taken so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::Board;
+    /// let mut board = Board::new(3, 3, 3);
+    /// for (player, row, col) in [(0, 0, 0), (1, 0, 1), (2, 2, 2), (0, 0, 0)] {
+    ///     board.player_move(player, row, col).unwrap();
+    ///     board.settle();
+    /// }
+    /// assert_eq!(board.eliminations(), vec![(1, 4)]);
+    /// board.resign(2).unwrap();
+    /// assert_eq!(board.eliminations(), vec![(1, 4), (2, 4)]);
+    /// ```
+    pub fn eliminations(&self) -> Vec<(u8, usize)> {
+        self.eliminated
+            .iter()
+            .flat_map(|(players, turn)| players.iter().map(move |player| (*player, *turn)))
+            .collect()
+    }
+
+    /// Provides ranking of players, best first.
+    ///
+    /// Players still in the game, or the winner once game is over, come
+    /// first and the player eliminated first comes last.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::{Board, Standing};
+    /// let mut board = Board::new(3, 3, 3);
+    /// board.player_move(0, 1, 1).unwrap();
+    /// board.resign(1).unwrap();
+    /// board.resign(2).unwrap();
+    /// assert_eq!(
+    ///     board.standings(),
+    ///     vec![
+    ///         Standing { player: 0, place: 1, eliminated: None },
+    ///         Standing { player: 2, place: 2, eliminated: Some(1) },
+    ///         Standing { player: 1, place: 3, eliminated: Some(1) },
+    ///     ]
+    /// );
+    /// ```
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = (0..self.player_count())
+            .filter(|player| self.eliminated.iter().all(|(out, _)| !out.contains(player)))
+            .map(|player| Standing {
+                player,
+                place: 1,
+                eliminated: None,
+            })
+            .collect();
+        for (players, turn) in self.eliminated.iter().rev() {
+            let place = standings.len() as u8 + 1;
+            standings.extend(players.iter().map(|&player| Standing {
+                player,
+                place,
+                eliminated: Some(*turn),
+            }));
+        }
+        standings
+    }
+
+    /// Provides what happens to atoms of players leaving the game.
+    pub fn leave_policy(&self) -> LeavePolicy {
+        self.leave_policy
+    }
+
+    /// Set what happens to atoms of players leaving the game.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::{Board, CellState, LeavePolicy};
+    ///
+    /// let mut board = Board::new(3, 3, 3).with_leave_policy(LeavePolicy::Neutral);
+    /// board.player_move(0, 1, 1).unwrap();
+    /// board.resign(0).unwrap();
+    /// // Atoms stay, but player 0 is out.
+    /// assert_eq!(board.cells()[1][1], CellState::NonEmpty(0, 1));
+    /// assert!(!board.is_player_alive(0));
+    /// ```
+    pub fn with_leave_policy(mut self, leave_policy: LeavePolicy) -> Self {
+        self.leave_policy = leave_policy;
+        self
+    }
+
+    /// Group players into teams, game is over once only one team remains.
+    ///
+    /// # Examples
+    ///
+    /// Players 0 and 2 play against players 1 and 3.
+    ///
+    /// ```
+    /// use chain_reaction::board::{Board, BoardState, CellState, Teams};
+    ///
+    /// let mut board = Board::new(3, 3, 4).with_teams(Teams::new(2));
+    /// assert_eq!(board.team(2), 0);
+    /// for (player, row, col) in [(0, 0, 0), (1, 2, 2), (2, 1, 0), (3, 2, 1), (0, 0, 0)] {
+    ///     board.player_move(player, row, col).unwrap();
+    ///     board.settle();
+    /// }
+    /// // Explosion of player 0 does not take cell of teammate.
+    /// assert_eq!(board.cells()[1][0], CellState::NonEmpty(2, 2));
+    /// assert_eq!(board.cells()[0][1], CellState::NonEmpty(0, 1));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are less than 2 teams or players can not be split
+    /// evenly between teams.
+    pub fn with_teams(mut self, teams: Teams) -> Self {
+        let players = self.player_count();
+        if teams.count < 2 || !players.is_multiple_of(teams.count) {
+            panic!("players should be split evenly into 2 or more teams");
+        }
+        self.last_moved = (0..teams.count)
+            .map(|team| team + players - teams.count)
+            .collect();
+        self.teams = Some(teams);
+        self
+    }
+
+    /// Provides cells where current player can make a move.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::Board;
+    /// let mut board = Board::new(3, 3, 2);
+    /// assert_eq!(board.valid_moves().len(), 9);
+    /// board.player_move(0, 0, 0).unwrap();
+    /// assert!(!board.valid_moves().contains(&(0, 0)));
+    /// ```
+    pub fn valid_moves(&self) -> Vec<(usize, usize)> {
+        if !matches!(self.state, BoardState::Wait) {
+            return vec![];
+        }
+        self.cells
+            .iter()
+            .enumerate()
+            .flat_map(|(r, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(
+                        |(_, cell)| !matches!(cell.owner, Some(owner) if owner != self.cur_player),
+                    )
+                    .map(move |(c, _)| (r, c))
+            })
+            .collect()
+    }
+
     /// Create new Board
     ///
     /// # Arguments
@@ -142,6 +555,25 @@ impl Board {
     /// ```
     ///
     pub fn new(rows: usize, cols: usize, players: u8) -> Self {
+        Self::with_rules(rows, cols, players, Rules::Classic)
+    }
+
+    /// Create new Board with given rules.
+    ///
+    /// # Examples
+    /// On wrapped board corners also need 4 atoms to explode.
+    /// ```
+    /// use chain_reaction::board::{Board, Rules};
+    /// let board = Board::with_rules(4, 4, 2, Rules::Wrap);
+    /// assert_eq!(board.critical_mass(0, 0), 4);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid board same as [`Board::new`].
+    ///
+    /// [`Board::new`]: self::Board::new
+    pub fn with_rules(rows: usize, cols: usize, players: u8, rules: Rules) -> Self {
         if rows < 3 || cols < 3 {
             panic!("rows and columns should be greater than 3");
         }
@@ -157,9 +589,96 @@ impl Board {
             cols,
             cells,
             players: vec![true; players.into()].into_boxed_slice(),
+            left: vec![false; players.into()].into_boxed_slice(),
+            draw_offers: vec![false; players.into()].into_boxed_slice(),
+            leave_policy: LeavePolicy::default(),
+            teams: None,
+            last_moved: (0..players).collect(),
+            turns: 0,
+            eliminated: vec![],
+            rules,
         }
     }
 
+    /// Create Board from existing position.
+    ///
+    /// All players are considered alive, players without cells are
+    /// eliminated after next move like at start of game.
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - Rows of [`CellState`], only `Empty` and `NonEmpty` are allowed.
+    /// * `players` - Number of players.
+    /// * `cur_player` - Player who moves next.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`PositionError`] if position is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::{Board, CellState, PositionError};
+    ///
+    /// let mut cells = vec![vec![CellState::Empty; 3]; 3];
+    /// cells[1][1] = CellState::NonEmpty(1, 3);
+    /// let board = Board::from_cells(cells.clone(), 2, 1).unwrap();
+    /// assert_eq!(board.cells(), cells);
+    /// assert_eq!(board.current_player_id(), 1);
+    ///
+    /// // Center cell explodes at 4 atoms.
+    /// cells[1][1] = CellState::NonEmpty(1, 4);
+    /// assert_eq!(
+    ///     Board::from_cells(cells, 2, 1).err(),
+    ///     Some(PositionError::InvalidAtoms(1, 1))
+    /// );
+    /// ```
+    ///
+    /// [`CellState`]: self::CellState
+    /// [`PositionError`]: self::PositionError
+    pub fn from_cells(
+        cells: Vec<Vec<CellState>>,
+        players: u8,
+        cur_player: u8,
+    ) -> Result<Self, PositionError> {
+        let rows = cells.len();
+        let cols = cells.first().map_or(0, |row| row.len());
+        if rows < 3 || cols < 3 {
+            return Err(PositionError::BoardTooSmall);
+        }
+        if cells.iter().any(|row| row.len() != cols) {
+            return Err(PositionError::UnevenRows);
+        }
+        if players < 2 {
+            return Err(PositionError::TooFewPlayers);
+        }
+        if cur_player >= players {
+            return Err(PositionError::UnknownPlayer);
+        }
+        let mut board = Self::new(rows, cols, players);
+        board.cur_player = cur_player;
+        for (r, row) in cells.into_iter().enumerate() {
+            for (c, cell) in row.into_iter().enumerate() {
+                match cell {
+                    CellState::Empty => {}
+                    CellState::NonEmpty(owner, _) if owner >= players => {
+                        return Err(PositionError::UnknownOwner(r, c));
+                    }
+                    CellState::NonEmpty(owner, atoms)
+                        if atoms > 0 && atoms < board.critical_mass(r, c) =>
+                    {
+                        board.cells[r][c] = Cell {
+                            owner: Some(owner),
+                            atoms,
+                        };
+                    }
+                    _ => return Err(PositionError::InvalidAtoms(r, c)),
+                }
+            }
+        }
+        Ok(board)
+    }
+
     /// Allow player to make a move on board.
     ///
     /// # Arguments
@@ -212,7 +731,7 @@ impl Board {
     /// assert_eq!(board.player_move(0, 0, 0), Err(MoveError::GameOver));
     /// ```
     pub fn player_move(&mut self, player: u8, row: usize, col: usize) -> Result<(), MoveError> {
-        if matches!(self.state, BoardState::GameOver(_)) {
+        if self.is_over() {
             Err(MoveError::GameOver)
         } else if !matches!(self.state, BoardState::Wait) {
             Err(MoveError::MoveNotComplete)
@@ -232,7 +751,9 @@ impl Board {
                     return Err(MoveError::OtherPlayersCell);
                 }
             }
-            if cell.add_atom(1, self.cur_player, row, col, self.rows, self.cols) {
+            let critical_mass = self.rules.critical_mass(row, col, self.rows, self.cols);
+            self.turns += 1;
+            if cell.add_atom(1, self.cur_player, critical_mass, None) {
                 self.state = BoardState::Explosion(vec![(row, col)])
             } else {
                 self.next_player()
@@ -241,40 +762,280 @@ impl Board {
         }
     }
 
+    /// Player gives up and leaves the game.
+    ///
+    /// Player no longer gets turns, atoms of player are cleared or stay
+    /// as neutral obstacles as [`LeavePolicy`] of board says. If player
+    /// was to move next, turn passes on. Game is over once one player
+    /// remains.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`MoveError`] if game is over, explosions of last move
+    /// are not over or player is not in the game.
+    ///
+    /// [`LeavePolicy`]: self::LeavePolicy
+    /// [`MoveError`]: crate::board::MoveError
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::{Board, BoardState, CellState, MoveError};
+    ///
+    /// let mut board = Board::new(3, 3, 3);
+    /// board.player_move(0, 0, 0).unwrap();
+    /// board.player_move(1, 1, 1).unwrap();
+    /// assert_eq!(board.resign(2), Ok(()));
+    /// assert_eq!(board.current_player_id(), 0);
+    /// assert_eq!(board.resign(2), Err(MoveError::PlayerOut));
+    /// assert_eq!(board.resign(0), Ok(()));
+    /// assert_eq!(board.cells()[0][0], CellState::Empty);
+    /// assert_eq!(board.state(), &BoardState::GameOver(1));
+    /// assert_eq!(board.valid_moves(), vec![]);
+    /// ```
+    pub fn resign(&mut self, player: u8) -> Result<(), MoveError> {
+        self.check_waiting(player)?;
+        self.left[player as usize] = true;
+        self.eliminated.push((vec![player], self.turns));
+        if self.leave_policy == LeavePolicy::Clear {
+            for cell in self.cells.iter_mut().flat_map(|row| row.iter_mut()) {
+                if cell.owner == Some(player) {
+                    *cell = Cell::default();
+                }
+            }
+        }
+        if self.teams_alive() == 1 {
+            self.game_over();
+        } else {
+            if self.cur_player == player {
+                self.next_player();
+            }
+            self.check_draw();
+        }
+        Ok(())
+    }
+
+    /// Removes player from the game, decided by host instead of player,
+    /// e.g. for not returning after losing connection.
+    ///
+    /// Outcome is same as of [`Board::resign`].
+    ///
+    /// # Errors
+    ///
+    /// Will return [`MoveError`] same as [`Board::resign`].
+    ///
+    /// [`Board::resign`]: self::Board::resign
+    /// [`MoveError`]: crate::board::MoveError
+    pub fn forfeit(&mut self, player: u8) -> Result<(), MoveError> {
+        self.resign(player)
+    }
+
+    /// Player offers a draw, or agrees to one offered before.
+    ///
+    /// Game is drawn once every player in the game agreed, offers stand
+    /// until someone declines. Players need not wait for their turn.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`MoveError`] if game is over, explosions of last move
+    /// are not over or player is not in the game.
+    ///
+    /// [`MoveError`]: crate::board::MoveError
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::{Board, BoardState, MoveError};
+    ///
+    /// let mut board = Board::new(3, 3, 2);
+    /// board.player_move(0, 0, 0).unwrap();
+    /// assert_eq!(board.accept_draw(1), Err(MoveError::NoDrawOffer));
+    /// assert_eq!(board.offer_draw(0), Ok(()));
+    /// assert_eq!(board.draw_offers(), vec![0]);
+    /// assert_eq!(board.decline_draw(1), Ok(()));
+    /// assert!(board.draw_offers().is_empty());
+    /// board.offer_draw(1).unwrap();
+    /// assert_eq!(board.accept_draw(0), Ok(()));
+    /// assert_eq!(board.state(), &BoardState::Draw);
+    /// assert_eq!(board.player_move(1, 2, 2), Err(MoveError::GameOver));
+    /// ```
+    pub fn offer_draw(&mut self, player: u8) -> Result<(), MoveError> {
+        self.check_waiting(player)?;
+        self.agree_draw(player);
+        Ok(())
+    }
+
+    /// Player agrees to draw offered by other player.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`MoveError`] if no draw is offered, or as
+    /// [`Board::offer_draw`] does.
+    ///
+    /// [`Board::offer_draw`]: self::Board::offer_draw
+    /// [`MoveError`]: crate::board::MoveError
+    pub fn accept_draw(&mut self, player: u8) -> Result<(), MoveError> {
+        self.check_waiting(player)?;
+        if self.draw_offers().is_empty() {
+            return Err(MoveError::NoDrawOffer);
+        }
+        self.agree_draw(player);
+        Ok(())
+    }
+
+    /// Player turns down draw offered, withdrawing all offers.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`MoveError`] same as [`Board::accept_draw`].
+    ///
+    /// [`Board::accept_draw`]: self::Board::accept_draw
+    /// [`MoveError`]: crate::board::MoveError
+    pub fn decline_draw(&mut self, player: u8) -> Result<(), MoveError> {
+        self.check_waiting(player)?;
+        if self.draw_offers().is_empty() {
+            return Err(MoveError::NoDrawOffer);
+        }
+        self.draw_offers.iter_mut().for_each(|offer| *offer = false);
+        Ok(())
+    }
+
+    /// Provides players in the game agreeing to a draw.
+    pub fn draw_offers(&self) -> Vec<u8> {
+        (0..self.player_count())
+            .filter(|p| self.draw_offers[*p as usize] && self.is_player_alive(*p))
+            .collect()
+    }
+
+    /// Checks player in the game may act between moves.
+    fn check_waiting(&self, player: u8) -> Result<(), MoveError> {
+        if self.is_over() {
+            Err(MoveError::GameOver)
+        } else if !matches!(self.state, BoardState::Wait) {
+            Err(MoveError::MoveNotComplete)
+        } else if !self.is_player_alive(player) {
+            Err(MoveError::PlayerOut)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Records player agreeing to draw, drawing game once everyone did.
+    fn agree_draw(&mut self, player: u8) {
+        self.draw_offers[player as usize] = true;
+        self.check_draw();
+    }
+
+    /// Draws game if every player in the game agreed to it.
+    fn check_draw(&mut self) {
+        let agreed = (0..self.player_count())
+            .filter(|p| self.is_player_alive(*p))
+            .all(|p| self.draw_offers[p as usize]);
+        if agreed {
+            self.state = BoardState::Draw;
+        }
+    }
+
+    /// Passes turn of player to next player without a move.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`MoveError`] if player could not move now.
+    ///
+    /// [`MoveError`]: crate::board::MoveError
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::{Board, MoveError};
+    ///
+    /// let mut board = Board::new(3, 3, 2);
+    /// assert_eq!(board.skip_turn(1), Err(MoveError::NotCurrentPlayerMove));
+    /// assert_eq!(board.skip_turn(0), Ok(()));
+    /// assert_eq!(board.current_player_id(), 1);
+    /// ```
+    pub fn skip_turn(&mut self, player: u8) -> Result<(), MoveError> {
+        if self.is_over() {
+            Err(MoveError::GameOver)
+        } else if !matches!(self.state, BoardState::Wait) {
+            Err(MoveError::MoveNotComplete)
+        } else if self.cur_player != player {
+            Err(MoveError::NotCurrentPlayerMove)
+        } else {
+            self.turns += 1;
+            self.next_player();
+            Ok(())
+        }
+    }
+
+    /// Passes turn to next team with players in the game, to the player
+    /// of that team after the one who moved last for it.
     fn next_player(&mut self) {
-        let mut i = self.cur_player as usize;
+        let team_count = self.last_moved.len();
         let player_count = self.players.len();
-        self.cur_player = loop {
-            i = (i + 1) % player_count;
-            if self.players[i] {
-                break i as u8;
-            }
-        };
+        let team = self.team(self.cur_player) as usize;
+        self.last_moved[team] = self.cur_player;
+        self.cur_player = (1..=team_count)
+            .map(|i| (team + i) % team_count)
+            .find_map(|team| {
+                let last = self.last_moved[team] as usize;
+                (1..=player_count / team_count)
+                    .map(|i| ((last + i * team_count) % player_count) as u8)
+                    .find(|player| self.is_player_alive(*player))
+            })
+            .expect("next player is searched while players are in the game");
         self.state = BoardState::Wait
     }
 
+    /// Provides number of teams with players in the game.
+    fn teams_alive(&self) -> usize {
+        let mut teams: Vec<u8> = (0..self.player_count())
+            .filter(|p| self.is_player_alive(*p))
+            .map(|p| self.team(p))
+            .collect();
+        teams.sort_unstable();
+        teams.dedup();
+        teams.len()
+    }
+
+    /// Ends game with current player as winner, or first player left in
+    /// the game if current player is out.
+    fn game_over(&mut self) {
+        if !self.is_player_alive(self.cur_player) {
+            self.cur_player = (0..self.player_count())
+                .find(|p| self.is_player_alive(*p))
+                .expect("game is over while players are in the game");
+        }
+        self.state = BoardState::GameOver(self.cur_player);
+    }
+
     /// Runs next iterations of explosion.
     ///
+    /// Explosion on board owned entirely by current player may never stop,
+    /// such explosion is cut short as the current player has already won.
+    ///
     /// Returns `true` if there is next iteration.
     pub fn next_iteration(&mut self) -> bool {
         match self.state {
             BoardState::Explosion(ref mut explosion) => {
+                let (rows, cols, rules) = (self.rows, self.cols, self.rules);
+                let (player, teams) = (self.cur_player, self.teams);
                 // TODO: improve traversal ??
                 let exploded_cells: Vec<_> = explosion
                     .drain(..)
-                    .flat_map(|(row, col)| Cell::get_neighbors(row, col, self.rows, self.cols))
+                    .flat_map(|(row, col)| rules.neighbors(row, col, rows, cols))
                     .filter(|(row, col)| {
                         self.cells[*row][*col].add_atom(
                             1,
-                            self.cur_player,
-                            *row,
-                            *col,
-                            self.rows,
-                            self.cols,
+                            player,
+                            rules.critical_mass(*row, *col, rows, cols),
+                            teams
+                                .filter(|teams| !teams.capture)
+                                .map(|teams| teams.count),
                         )
                     })
                     .collect();
-                self.state = if !exploded_cells.is_empty() {
+                self.state = if !exploded_cells.is_empty() && !self.owned_only_by_team(player) {
                     BoardState::Explosion(exploded_cells)
                 } else {
                     BoardState::CheckWinCondition
@@ -282,6 +1043,9 @@ impl Board {
                 true
             }
             BoardState::CheckWinCondition => {
+                let was_alive: Vec<bool> = (0..self.player_count())
+                    .map(|p| self.is_player_alive(p))
+                    .collect();
                 self.players.iter_mut().for_each(|i| *i = false);
                 for rows in self.cells.iter() {
                     for cell in rows.iter() {
@@ -290,8 +1054,14 @@ impl Board {
                         }
                     }
                 }
-                if self.players.iter().filter(|x| **x).count() == 1 {
-                    self.state = BoardState::GameOver(self.cur_player);
+                let out: Vec<u8> = (0..self.player_count())
+                    .filter(|p| was_alive[*p as usize] && !self.is_player_alive(*p))
+                    .collect();
+                if !out.is_empty() {
+                    self.eliminated.push((out, self.turns));
+                }
+                if self.teams_alive() == 1 {
+                    self.game_over();
                 } else {
                     self.next_player();
                 }
@@ -300,6 +1070,38 @@ impl Board {
             _ => false,
         }
     }
+
+    /// Runs explosions till board waits for next move or game is over.
+    ///
+    /// Returns number of explosion waves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::{Board, BoardState};
+    ///
+    /// let mut board = Board::new(4, 4, 2);
+    /// board.player_move(0, 0, 0).unwrap();
+    /// board.player_move(1, 1, 0).unwrap();
+    /// board.player_move(0, 0, 0).unwrap();
+    /// assert_eq!(board.settle(), 1);
+    /// assert_eq!(board.state(), &BoardState::GameOver(0));
+    /// ```
+    pub fn settle(&mut self) -> usize {
+        let mut waves = 0;
+        while self.next_iteration() {
+            waves += 1;
+        }
+        waves
+    }
+
+    fn owned_only_by_team(&self, player: u8) -> bool {
+        let team = self.team(player);
+        self.cells
+            .iter()
+            .flat_map(|row| row.iter())
+            .all(|cell| !matches!(cell.owner, Some(owner) if self.team(owner) != team))
+    }
 }
 
 impl Display for Board {
@@ -344,4 +1146,104 @@ mod tests {
         // the player cannot play.
         assert_ne!(b.current_player_id(), player_lost_all_cells);
     }
+
+    #[test]
+    fn draw_needs_everyone_in_game() {
+        let mut b = Board::new(3, 3, 3);
+        b.offer_draw(0).unwrap();
+        b.accept_draw(1).unwrap();
+        assert_eq!(b.state(), &BoardState::Wait);
+        // Only players left in the game had to agree.
+        b.resign(2).unwrap();
+        assert_eq!(b.state(), &BoardState::Draw);
+        assert!(b.is_over());
+        assert_eq!(b.offer_draw(0), Err(MoveError::GameOver));
+        let places: Vec<_> = b.standings().iter().map(|s| (s.player, s.place)).collect();
+        assert_eq!(places, vec![(0, 1), (1, 1), (2, 3)]);
+    }
+
+    #[test]
+    fn players_eliminated_together_share_place() {
+        let mut b = Board::new(3, 3, 3);
+        for (player, r, c) in [(0, 0, 0), (1, 0, 1), (2, 1, 0), (0, 0, 0)] {
+            b.player_move(player, r, c).unwrap();
+            b.settle();
+        }
+        assert_eq!(b.state(), &BoardState::GameOver(0));
+        assert_eq!(b.turns(), 4);
+        let places: Vec<_> = b
+            .standings()
+            .iter()
+            .map(|s| (s.player, s.place, s.eliminated))
+            .collect();
+        assert_eq!(places, vec![(0, 1, None), (1, 2, Some(4)), (2, 2, Some(4))]);
+    }
+
+    #[test]
+    fn neutral_atoms_are_captured() {
+        let mut b = Board::new(3, 3, 3).with_leave_policy(LeavePolicy::Neutral);
+        for (player, r, c) in [(0, 0, 0), (1, 0, 1), (2, 2, 2)] {
+            b.player_move(player, r, c).unwrap();
+        }
+        b.resign(1).unwrap();
+        assert_eq!(b.cells()[0][1], CellState::NonEmpty(1, 1));
+        // Other players cannot place atoms on neutral cells.
+        assert_eq!(b.player_move(0, 0, 1), Err(MoveError::OtherPlayersCell));
+        b.player_move(0, 0, 0).unwrap();
+        b.settle();
+        assert_eq!(b.cells()[0][1], CellState::NonEmpty(0, 2));
+        assert_eq!(b.current_player_id(), 2);
+        assert!(!b.is_player_alive(1));
+    }
+
+    #[test]
+    fn teams_keep_alternating_turns() {
+        let mut b = Board::new(3, 3, 4).with_teams(Teams::new(2));
+        b.resign(1).unwrap();
+        let mut turns = vec![];
+        for _ in 0..4 {
+            turns.push(b.current_player_id());
+            b.skip_turn(b.current_player_id()).unwrap();
+        }
+        assert_eq!(turns, vec![0, 3, 2, 3]);
+        b.resign(0).unwrap();
+        assert_eq!(b.state(), &BoardState::Wait);
+        b.resign(3).unwrap();
+        assert_eq!(b.state(), &BoardState::GameOver(2));
+    }
+
+    #[test]
+    fn teammates_capture_if_allowed() {
+        let teams = Teams {
+            count: 2,
+            capture: true,
+        };
+        let mut b = Board::new(3, 3, 4).with_teams(teams);
+        for (player, r, c) in [(0, 0, 0), (1, 2, 2), (2, 0, 1), (3, 2, 1), (0, 0, 0)] {
+            b.player_move(player, r, c).unwrap();
+            b.settle();
+        }
+        assert_eq!(b.cells()[0][1], CellState::NonEmpty(0, 2));
+        assert!(!b.is_player_alive(2));
+        assert_eq!(b.current_player_id(), 1);
+    }
+
+    #[test]
+    fn explosion_stops_when_player_owns_board() {
+        // Every cell of player 0 is one atom away from exploding.
+        let mut cells = vec![vec![CellState::NonEmpty(0, 2); 3]; 3];
+        cells[0][0] = CellState::NonEmpty(1, 1);
+        for (r, c) in [(0, 2), (2, 0), (2, 2)] {
+            cells[r][c] = CellState::NonEmpty(0, 1);
+        }
+        cells[1][1] = CellState::NonEmpty(0, 3);
+        let mut b = Board::from_cells(cells, 2, 0).unwrap();
+        b.player_move(0, 1, 1).unwrap();
+        let mut waves = 0;
+        while b.next_iteration() {
+            waves += 1;
+            assert!(waves < 100, "explosion never stops");
+        }
+        assert_eq!(b.state(), &BoardState::GameOver(0));
+    }
 }
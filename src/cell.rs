@@ -8,26 +8,22 @@ pub(crate) struct Cell {
 }
 
 impl Cell {
+    /// Adds atoms of player, taking over the cell unless owned by teammate.
+    ///
+    /// `teams` is number of teams when teammates keep their cells.
     pub(crate) fn add_atom(
         &mut self,
         new: u8,
         player: u8,
-        row: usize,
-        col: usize,
-        row_max: usize,
-        col_max: usize,
+        critical_mass: u8,
+        teams: Option<u8>,
     ) -> bool {
-        let mut critical_mass = 4;
-        self.owner = Some(player);
-
-        // top down edge
-        if row == 0 || row + 1 == row_max {
-            critical_mass -= 1;
-        }
-
-        // right left edge
-        if col == 0 || col + 1 == col_max {
-            critical_mass -= 1;
+        let teammate = matches!(
+            (self.owner, teams),
+            (Some(owner), Some(teams)) if owner % teams == player % teams
+        );
+        if !teammate {
+            self.owner = Some(player);
         }
         self.atoms += new;
 
@@ -43,6 +39,21 @@ impl Cell {
         }
     }
 
+    pub(crate) fn critical_mass(row: usize, col: usize, row_max: usize, col_max: usize) -> u8 {
+        let mut critical_mass = 4;
+
+        // top down edge
+        if row == 0 || row + 1 == row_max {
+            critical_mass -= 1;
+        }
+
+        // right left edge
+        if col == 0 || col + 1 == col_max {
+            critical_mass -= 1;
+        }
+        critical_mass
+    }
+
     pub(crate) fn get_neighbors(
         row: usize,
         col: usize,
@@ -64,6 +75,20 @@ impl Cell {
         }
         explosion
     }
+
+    pub(crate) fn get_wrapped_neighbors(
+        row: usize,
+        col: usize,
+        row_max: usize,
+        col_max: usize,
+    ) -> Vec<(usize, usize)> {
+        vec![
+            ((row + row_max - 1) % row_max, col),
+            ((row + 1) % row_max, col),
+            (row, (col + col_max - 1) % col_max),
+            (row, (col + 1) % col_max),
+        ]
+    }
 }
 
 #[cfg(test)]
@@ -90,4 +115,12 @@ mod tests {
             vec![(0, 1), (2, 1), (1, 0), (1, 2)]
         )
     }
+
+    #[test]
+    fn test_wrapped_neighbour_for_corner_cell() {
+        assert_eq!(
+            Cell::get_wrapped_neighbors(0, 0, 10, 10),
+            vec![(9, 0), (1, 0), (0, 9), (0, 1)]
+        )
+    }
 }
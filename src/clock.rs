@@ -0,0 +1,411 @@
+//! Provides time controls for games, enforced by clock wrapping board.
+//!
+//! Players may have limited time for every move, for the whole game or
+//! both. Time of player runs only while board waits for the player's move,
+//! not during explosions. What happens to player running out of time is
+//! decided by [`TimeoutAction`].
+//!
+//! Clock does not read time itself, current instant is passed to it, so
+//! host decides how often timeouts are checked with [`GameClock::tick`].
+//!
+//! # Examples
+//!
+//! ```
+//! use chain_reaction::board::Board;
+//! use chain_reaction::clock::{GameClock, TimeControl, Timeout, TimeoutAction};
+//! use std::time::{Duration, Instant};
+//!
+//! let control = TimeControl {
+//!     per_move: Some(Duration::from_secs(10)),
+//!     per_game: None,
+//!     on_timeout: TimeoutAction::Skip,
+//! };
+//! let start = Instant::now();
+//! let secs = |secs| start + Duration::from_secs(secs);
+//! let mut clock = GameClock::new(Board::new(3, 3, 2), control, start);
+//! clock.player_move(0, 0, 0, secs(4)).unwrap();
+//! clock.settle(secs(4));
+//!
+//! // Player 1 has 10 seconds from then.
+//! assert_eq!(clock.time_left(1, secs(5)), Some(Duration::from_secs(9)));
+//! assert_eq!(clock.tick(secs(13)), None);
+//! assert_eq!(clock.tick(secs(14)), Some(Timeout::Skipped(1)));
+//! assert_eq!(clock.board().current_player_id(), 0);
+//! ```
+
+use crate::board::{Board, BoardState, MoveError};
+use crate::rng::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// What happens to player running out of time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeoutAction {
+    /// Player is out of the game.
+    #[default]
+    Forfeit,
+    /// Random valid move is made for player.
+    #[serde(rename = "random")]
+    RandomMove,
+    /// Turn passes to next player without move.
+    Skip,
+}
+
+impl TimeoutAction {
+    /// Names of all timeout actions.
+    pub const NAMES: [&'static str; 3] = ["forfeit", "random", "skip"];
+
+    /// Provides timeout action by name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::clock::TimeoutAction;
+    /// assert_eq!(TimeoutAction::from_name("random"), Some(TimeoutAction::RandomMove));
+    /// assert_eq!(TimeoutAction::from_name("draw"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "forfeit" => Some(Self::Forfeit),
+            "random" => Some(Self::RandomMove),
+            "skip" => Some(Self::Skip),
+            _ => None,
+        }
+    }
+
+    /// Provides name of timeout action.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Forfeit => "forfeit",
+            Self::RandomMove => "random",
+            Self::Skip => "skip",
+        }
+    }
+}
+
+/// Time limits of game, untimed by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeControl {
+    /// Time for each move.
+    pub per_move: Option<Duration>,
+    /// Time for all moves of player in the game.
+    pub per_game: Option<Duration>,
+    /// What happens to player running out of time.
+    pub on_timeout: TimeoutAction,
+}
+
+impl TimeControl {
+    /// Whether game has any time limit.
+    pub fn is_timed(&self) -> bool {
+        self.per_move.is_some() || self.per_game.is_some()
+    }
+}
+
+/// Formats time left as minutes and seconds, partial seconds count as whole.
+///
+/// # Examples
+///
+/// ```
+/// use chain_reaction::clock::minutes;
+/// use std::time::Duration;
+/// assert_eq!(minutes(Duration::from_millis(65_200)), "1:06");
+/// assert_eq!(minutes(Duration::ZERO), "0:00");
+/// ```
+pub fn minutes(time: Duration) -> String {
+    let secs = time.as_millis().div_ceil(1000);
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// Action taken by clock for player who ran out of time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timeout {
+    /// Player is out of the game. The player id is stored.
+    Forfeited(u8),
+    /// Random move was made for player.
+    Moved {
+        /// Player who ran out of time.
+        player: u8,
+        /// Row of cell.
+        row: usize,
+        /// Column of cell.
+        col: usize,
+    },
+    /// Turn of player was passed. The player id is stored.
+    Skipped(u8),
+}
+
+/// Board with time limits for players.
+///
+/// Moves are made through clock, so that time used is charged to player.
+#[derive(Clone)]
+pub struct GameClock {
+    board: Board,
+    control: TimeControl,
+    /// Game time left of each player, not counting current turn.
+    banks: Vec<Duration>,
+    /// When current turn started, `None` during explosions and after game.
+    started: Option<Instant>,
+    rng: Rng,
+}
+
+impl GameClock {
+    /// Create clock for board, first turn starts at given instant.
+    pub fn new(board: Board, control: TimeControl, now: Instant) -> Self {
+        let bank = control.per_game.unwrap_or(Duration::MAX);
+        let mut clock = Self {
+            banks: vec![bank; board.player_count() as usize],
+            board,
+            control,
+            started: None,
+            rng: Rng::new(0),
+        };
+        clock.restart(now);
+        clock
+    }
+
+    /// Set seed for picking random moves of players running out of time.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Rng::new(seed);
+        self
+    }
+
+    /// Provides board of game.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Provides time limits of game.
+    pub fn control(&self) -> TimeControl {
+        self.control
+    }
+
+    /// Starts turn of current player, if board is waiting for it.
+    fn restart(&mut self, now: Instant) {
+        self.started = matches!(self.board.state(), BoardState::Wait).then_some(now);
+    }
+
+    /// Time player has for whole turn.
+    fn allowed(&self, player: u8) -> Option<Duration> {
+        let bank = self.control.per_game.map(|_| self.banks[player as usize]);
+        match (self.control.per_move, bank) {
+            (Some(per_move), Some(bank)) => Some(per_move.min(bank)),
+            (per_move, bank) => per_move.or(bank),
+        }
+    }
+
+    /// Provides instant turn of current player is over, if game is timed.
+    pub fn deadline(&self) -> Option<Instant> {
+        let allowed = self.allowed(self.board.current_player_id())?;
+        self.started?.checked_add(allowed)
+    }
+
+    /// Provides time player has left for turn, if game is timed.
+    ///
+    /// Players waiting for their turn have all of it left.
+    pub fn time_left(&self, player: u8, now: Instant) -> Option<Duration> {
+        let allowed = self.allowed(player)?;
+        match self.started {
+            Some(started) if player == self.board.current_player_id() => {
+                Some(allowed.saturating_sub(now.saturating_duration_since(started)))
+            }
+            _ => Some(allowed),
+        }
+    }
+
+    /// Charges time of turn to player.
+    fn charge(&mut self, player: u8, now: Instant) {
+        if let Some(started) = self.started.take() {
+            let bank = &mut self.banks[player as usize];
+            *bank = bank.saturating_sub(now.saturating_duration_since(started));
+        }
+    }
+
+    /// Allow player to make a move on board, see [`Board::player_move`].
+    ///
+    /// # Errors
+    ///
+    /// Will return [`MoveError::OutOfTime`] if turn of player is over,
+    /// [`GameClock::tick`] deals with the player.
+    ///
+    /// [`Board::player_move`]: crate::board::Board::player_move
+    /// [`MoveError::OutOfTime`]: crate::board::MoveError::OutOfTime
+    pub fn player_move(
+        &mut self,
+        player: u8,
+        row: usize,
+        col: usize,
+        now: Instant,
+    ) -> Result<(), MoveError> {
+        let out_of_time = matches!(self.deadline(), Some(deadline) if now >= deadline);
+        if out_of_time && player == self.board.current_player_id() {
+            return Err(MoveError::OutOfTime);
+        }
+        self.board.player_move(player, row, col)?;
+        self.charge(player, now);
+        self.restart(now);
+        Ok(())
+    }
+
+    /// Runs next iteration of explosion, see [`Board::next_iteration`].
+    ///
+    /// Turn of next player starts once explosions are over.
+    ///
+    /// [`Board::next_iteration`]: crate::board::Board::next_iteration
+    pub fn next_iteration(&mut self, now: Instant) -> bool {
+        let next = self.board.next_iteration();
+        self.restart(now);
+        next
+    }
+
+    /// Runs explosions till board waits for next move or game is over.
+    ///
+    /// Returns number of explosion waves.
+    pub fn settle(&mut self, now: Instant) -> usize {
+        let waves = self.board.settle();
+        self.restart(now);
+        waves
+    }
+
+    /// Player gives up and leaves the game, see [`Board::resign`].
+    ///
+    /// [`Board::resign`]: crate::board::Board::resign
+    pub fn resign(&mut self, player: u8, now: Instant) -> Result<(), MoveError> {
+        let turn = self.board.current_player_id();
+        self.board.resign(player)?;
+        if turn == player || self.board.is_over() {
+            self.restart(now);
+        }
+        Ok(())
+    }
+
+    /// Removes player from the game, see [`Board::forfeit`].
+    ///
+    /// [`Board::forfeit`]: crate::board::Board::forfeit
+    pub fn forfeit(&mut self, player: u8, now: Instant) -> Result<(), MoveError> {
+        self.resign(player, now)
+    }
+
+    /// Player offers a draw, see [`Board::offer_draw`].
+    ///
+    /// [`Board::offer_draw`]: crate::board::Board::offer_draw
+    pub fn offer_draw(&mut self, player: u8, now: Instant) -> Result<(), MoveError> {
+        self.board.offer_draw(player)?;
+        if self.board.is_over() {
+            self.restart(now);
+        }
+        Ok(())
+    }
+
+    /// Player agrees to draw offered, see [`Board::accept_draw`].
+    ///
+    /// [`Board::accept_draw`]: crate::board::Board::accept_draw
+    pub fn accept_draw(&mut self, player: u8, now: Instant) -> Result<(), MoveError> {
+        self.board.accept_draw(player)?;
+        if self.board.is_over() {
+            self.restart(now);
+        }
+        Ok(())
+    }
+
+    /// Player turns down draw offered, see [`Board::decline_draw`].
+    ///
+    /// [`Board::decline_draw`]: crate::board::Board::decline_draw
+    pub fn decline_draw(&mut self, player: u8) -> Result<(), MoveError> {
+        self.board.decline_draw(player)
+    }
+
+    /// Deals with current player if turn is over, provides what was done.
+    ///
+    /// After random move explosions are yet to be run.
+    pub fn tick(&mut self, now: Instant) -> Option<Timeout> {
+        let deadline = self.deadline()?;
+        if now < deadline {
+            return None;
+        }
+        let player = self.board.current_player_id();
+        self.charge(player, deadline);
+        let moves = self.board.valid_moves();
+        let timeout = match self.control.on_timeout {
+            TimeoutAction::RandomMove if !moves.is_empty() => {
+                let (row, col) = moves[self.rng.below(moves.len())];
+                self.board
+                    .player_move(player, row, col)
+                    .expect("valid move of current player");
+                Timeout::Moved { player, row, col }
+            }
+            TimeoutAction::Forfeit => {
+                self.board
+                    .forfeit(player)
+                    .expect("current player is in the game");
+                Timeout::Forfeited(player)
+            }
+            TimeoutAction::RandomMove | TimeoutAction::Skip => {
+                self.board
+                    .skip_turn(player)
+                    .expect("board waits for current player");
+                Timeout::Skipped(player)
+            }
+        };
+        self.restart(now);
+        Some(timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(per_move: u64, per_game: u64, on_timeout: TimeoutAction) -> (GameClock, Instant) {
+        let secs = |secs| (secs > 0).then(|| Duration::from_secs(secs));
+        let control = TimeControl {
+            per_move: secs(per_move),
+            per_game: secs(per_game),
+            on_timeout,
+        };
+        let start = Instant::now();
+        let board = Board::new(3, 3, 3);
+        (GameClock::new(board, control, start), start)
+    }
+
+    #[test]
+    fn game_time_is_used_up() {
+        let (mut clock, start) = clock(10, 15, TimeoutAction::Forfeit);
+        let secs = |secs| start + Duration::from_secs(secs);
+        assert_eq!(clock.time_left(0, secs(2)), Some(Duration::from_secs(8)));
+        clock.player_move(0, 0, 0, secs(8)).unwrap();
+        clock.player_move(1, 1, 1, secs(8)).unwrap();
+        clock.player_move(2, 2, 2, secs(8)).unwrap();
+        // Only 7 seconds of game time are left.
+        assert_eq!(clock.time_left(0, secs(8)), Some(Duration::from_secs(7)));
+        assert_eq!(clock.deadline(), Some(secs(15)));
+        assert_eq!(
+            clock.player_move(0, 0, 0, secs(15)),
+            Err(MoveError::OutOfTime)
+        );
+        assert_eq!(clock.tick(secs(15)), Some(Timeout::Forfeited(0)));
+        assert!(!clock.board().is_player_alive(0));
+        assert_eq!(clock.board().current_player_id(), 1);
+        assert_eq!(clock.deadline(), Some(secs(25)));
+    }
+
+    #[test]
+    fn random_move_is_made() {
+        let (mut clock, start) = clock(5, 0, TimeoutAction::RandomMove);
+        let timeout = clock.tick(start + Duration::from_secs(5));
+        let Some(Timeout::Moved { player, row, col }) = timeout else {
+            panic!("random move expected");
+        };
+        assert_eq!(player, 0);
+        assert!(clock.board().cells()[row][col] != crate::board::CellState::Empty);
+        assert_eq!(clock.board().current_player_id(), 1);
+    }
+
+    #[test]
+    fn untimed_game_never_times_out() {
+        let (mut clock, start) = clock(0, 0, TimeoutAction::Forfeit);
+        assert_eq!(clock.deadline(), None);
+        assert_eq!(clock.time_left(0, start), None);
+        assert_eq!(clock.tick(start + Duration::from_secs(3600)), None);
+    }
+}
@@ -0,0 +1,559 @@
+//! Provides line based protocol for bots playing over stdin and stdout.
+//!
+//! Host starts bot process, tells it the game and asks it for moves, one
+//! message per line. Moves are row and column, the player is the one whose
+//! turn it is.
+//!
+//! ```text
+//! host:   newgame 10 10 2 classic   (rows, columns, players and optional rules)
+//! host:   isready
+//! engine: readyok
+//! host:   position 4,4 -1 0,0       (turns so far, may be none, -1 if player 1 resigned)
+//! host:   go time 1000              (milliseconds to think)
+//! engine: bestmove 3 3
+//! host:   quit
+//! ```
+//!
+//! Engine answers `error <reason>` instead of a move when game or position
+//! is invalid, and to lines it does not understand. Other lines from engine
+//! are ignored by host.
+//!
+//! Engine answers messages in order, so once a move comes too late host asks
+//! `isready` before its next question and drops answers till `readyok`.
+//!
+//! # Examples
+//!
+//! ```
+//! use chain_reaction::ai;
+//! use chain_reaction::engine::Engine;
+//!
+//! let mut engine = Engine::new(ai::from_name("greedy", 0).unwrap());
+//! let mut output = vec![];
+//! let input = "newgame 3 3 2\nposition 0,0\ngo time 100\nquit\n";
+//! engine.run(input.as_bytes(), &mut output).unwrap();
+//! let output = String::from_utf8(output).unwrap();
+//! assert!(output.starts_with("bestmove "));
+//! ```
+
+use crate::ai::Player;
+use crate::board::{Board, BoardState, Rules, MAX_PLAYERS, MAX_SIZE, MIN_SIZE};
+use crate::record::Turn;
+use std::fmt::Display;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+/// Extra time given to engine for answering over the time to think.
+const GRACE: Duration = Duration::from_secs(1);
+
+/// Engine Errors.
+#[derive(Debug, PartialEq, Eq)]
+pub enum EngineError {
+    /// Message could not be understood. The line is stored.
+    InvalidMessage(String),
+    /// Engine process could not be started or talked to.
+    Io(std::io::ErrorKind),
+    /// Engine closed its output.
+    Disconnected,
+    /// Engine did not answer in time.
+    Timeout,
+    /// Engine answered with error. The reason is stored.
+    Engine(String),
+}
+
+impl From<std::io::Error> for EngineError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err.kind())
+    }
+}
+
+/// Message of protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// Start game with rows, columns, players and rules.
+    NewGame(usize, usize, u8, Rules),
+    /// Ask whether engine is ready.
+    IsReady,
+    /// Engine is ready.
+    ReadyOk,
+    /// Turns played so far, moves as row and column.
+    Position(Vec<Turn>),
+    /// Ask for move with time to think.
+    Go(Duration),
+    /// Move chosen by engine as row and column.
+    BestMove(usize, usize),
+    /// Message could not be handled. The reason is stored.
+    Error(String),
+    /// Stop engine.
+    Quit,
+}
+
+impl Message {
+    /// Parse message from line.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`EngineError::InvalidMessage`] if line is not a message.
+    ///
+    /// [`EngineError::InvalidMessage`]: self::EngineError::InvalidMessage
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::engine::Message;
+    /// use std::time::Duration;
+    ///
+    /// assert_eq!(Message::parse("bestmove 2 3"), Ok(Message::BestMove(2, 3)));
+    /// let go = Message::Go(Duration::from_millis(500));
+    /// assert_eq!(Message::parse(&go.to_string()), Ok(go));
+    /// ```
+    pub fn parse(line: &str) -> Result<Self, EngineError> {
+        let invalid = || EngineError::InvalidMessage(line.trim().to_string());
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let message = match words[..] {
+            ["newgame", rows, cols, players, ref rules @ ..] => {
+                let rules = match rules {
+                    [] => Rules::Classic,
+                    [name] => Rules::from_name(name).ok_or_else(invalid)?,
+                    _ => return Err(invalid()),
+                };
+                Self::NewGame(
+                    rows.parse().map_err(|_| invalid())?,
+                    cols.parse().map_err(|_| invalid())?,
+                    players.parse().map_err(|_| invalid())?,
+                    rules,
+                )
+            }
+            ["isready"] => Self::IsReady,
+            ["readyok"] => Self::ReadyOk,
+            ["position", ref turns @ ..] => Self::Position(
+                turns
+                    .iter()
+                    .map(|turn| match turn.strip_prefix('-') {
+                        Some(player) => Some(Turn::Resign(player.parse().ok()?)),
+                        None => {
+                            let (row, col) = turn.split_once(',')?;
+                            Some(Turn::Move(row.parse().ok()?, col.parse().ok()?))
+                        }
+                    })
+                    .collect::<Option<_>>()
+                    .ok_or_else(invalid)?,
+            ),
+            ["go", "time", ms] => {
+                Self::Go(Duration::from_millis(ms.parse().map_err(|_| invalid())?))
+            }
+            ["bestmove", row, col] => Self::BestMove(
+                row.parse().map_err(|_| invalid())?,
+                col.parse().map_err(|_| invalid())?,
+            ),
+            ["error", ..] => Self::Error(line.trim()["error".len()..].trim().to_string()),
+            ["quit"] => Self::Quit,
+            _ => return Err(invalid()),
+        };
+        Ok(message)
+    }
+}
+
+impl Display for Message {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NewGame(rows, cols, players, rules) => {
+                write!(f, "newgame {} {} {} {}", rows, cols, players, rules.name())
+            }
+            Self::IsReady => write!(f, "isready"),
+            Self::ReadyOk => write!(f, "readyok"),
+            Self::Position(turns) => {
+                write!(f, "position")?;
+                for turn in turns {
+                    match turn {
+                        Turn::Move(row, col) => write!(f, " {},{}", row, col)?,
+                        Turn::Resign(player) => write!(f, " -{}", player)?,
+                    }
+                }
+                Ok(())
+            }
+            Self::Go(time) => write!(f, "go time {}", time.as_millis()),
+            Self::BestMove(row, col) => write!(f, "bestmove {} {}", row, col),
+            Self::Error(reason) => write!(f, "error {}", reason),
+            Self::Quit => write!(f, "quit"),
+        }
+    }
+}
+
+/// Engine side of protocol, answering with moves of a computer player.
+///
+/// Only `isready` and `go` are answered, so that host always gets one answer
+/// for each question. Problems with `newgame` or `position` are reported
+/// when answering `go`.
+pub struct Engine {
+    player: Box<dyn Player>,
+    game: Option<(usize, usize, u8, Rules)>,
+    board: Result<Board, String>,
+}
+
+impl Engine {
+    /// Create engine choosing moves with given player.
+    pub fn new(player: Box<dyn Player>) -> Self {
+        Self {
+            player,
+            game: None,
+            board: Err("no newgame".to_string()),
+        }
+    }
+
+    fn position(&self, turns: Vec<Turn>) -> Result<Board, String> {
+        let (rows, cols, players, rules) = self.game.ok_or("no newgame")?;
+        let mut board = Board::with_rules(rows, cols, players, rules);
+        for (number, turn) in turns.into_iter().enumerate() {
+            match turn {
+                Turn::Move(row, col) => {
+                    board
+                        .player_move(board.current_player_id(), row, col)
+                        .map_err(|err| format!("turn {} is invalid: {:?}", number + 1, err))?;
+                    board.settle();
+                }
+                Turn::Resign(player) => board
+                    .resign(player)
+                    .map_err(|err| format!("turn {} is invalid: {:?}", number + 1, err))?,
+            }
+        }
+        Ok(board)
+    }
+
+    /// Handles message from host, provides answer if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::ai;
+    /// use chain_reaction::board::Rules;
+    /// use chain_reaction::engine::{Engine, Message};
+    /// use std::time::Duration;
+    ///
+    /// let mut engine = Engine::new(ai::from_name("random", 0).unwrap());
+    /// let go = Message::Go(Duration::from_millis(10));
+    /// assert_eq!(engine.handle(Message::Position(vec![])), None);
+    /// assert!(matches!(engine.handle(go.clone()), Some(Message::Error(_))));
+    /// assert_eq!(engine.handle(Message::NewGame(3, 3, 2, Rules::Classic)), None);
+    /// assert_eq!(engine.handle(Message::IsReady), Some(Message::ReadyOk));
+    /// assert!(matches!(engine.handle(go), Some(Message::BestMove(..))));
+    /// ```
+    pub fn handle(&mut self, message: Message) -> Option<Message> {
+        match message {
+            Message::NewGame(rows, cols, players, rules) => {
+                let sizes = MIN_SIZE..=MAX_SIZE;
+                if !sizes.contains(&rows) || !sizes.contains(&cols) {
+                    self.game = None;
+                    self.board = Err(format!(
+                        "board needs {} to {} rows and columns",
+                        MIN_SIZE, MAX_SIZE
+                    ));
+                } else if !(2..=MAX_PLAYERS).contains(&players) {
+                    self.game = None;
+                    self.board = Err(format!("game needs 2 to {} players", MAX_PLAYERS));
+                } else {
+                    self.game = Some((rows, cols, players, rules));
+                    self.board = Ok(Board::with_rules(rows, cols, players, rules));
+                }
+                None
+            }
+            Message::IsReady => Some(Message::ReadyOk),
+            Message::Position(turns) => {
+                self.board = self.position(turns);
+                None
+            }
+            Message::Go(_) => Some(match self.board {
+                Ok(ref board) if board.state() == &BoardState::Wait => {
+                    match self.player.choose_move(board) {
+                        Some((row, col)) => Message::BestMove(row, col),
+                        None => Message::Error("no move found".to_string()),
+                    }
+                }
+                Ok(_) => Message::Error("game is over".to_string()),
+                Err(ref reason) => Message::Error(reason.clone()),
+            }),
+            Message::Quit => None,
+            Message::ReadyOk | Message::BestMove(..) | Message::Error(_) => {
+                Some(Message::Error("unexpected message".to_string()))
+            }
+        }
+    }
+
+    /// Answers messages read from input till `quit` or end of input.
+    ///
+    /// # Errors
+    ///
+    /// Will return error if reading input or writing output fails.
+    pub fn run(&mut self, input: impl BufRead, mut output: impl Write) -> std::io::Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let answer = match Message::parse(&line) {
+                Ok(Message::Quit) => break,
+                Ok(message) => self.handle(message),
+                Err(_) => Some(Message::Error(format!("unknown message {}", line.trim()))),
+            };
+            if let Some(answer) = answer {
+                writeln!(output, "{}", answer)?;
+                output.flush()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Host side of protocol, asking engine process for moves.
+pub struct Host {
+    name: String,
+    child: Child,
+    input: std::process::ChildStdin,
+    lines: Receiver<String>,
+    /// `isready` sent without `readyok` yet.
+    readies: usize,
+    /// Answers to questions which failed may still come.
+    stale: bool,
+}
+
+impl Host {
+    /// Start engine process from command line, program followed by arguments.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`EngineError`] if process cannot be started.
+    ///
+    /// [`EngineError`]: self::EngineError
+    pub fn spawn(command: &str) -> Result<Self, EngineError> {
+        let mut words = command.split_whitespace();
+        let program = words
+            .next()
+            .ok_or(EngineError::Io(std::io::ErrorKind::NotFound))?;
+        let mut child = Command::new(program)
+            .args(words)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let input = child.stdin.take().expect("stdin is piped");
+        let output = child.stdout.take().expect("stdout is piped");
+        let (sender, lines) = mpsc::channel();
+        // Lines are read on separate thread so that waiting can time out.
+        std::thread::spawn(move || {
+            for line in BufReader::new(output).lines() {
+                let Ok(line) = line else { break };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self {
+            name: command.to_string(),
+            child,
+            input,
+            lines,
+            readies: 0,
+            stale: false,
+        })
+    }
+
+    /// Provides command line engine was started with.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn send(&mut self, message: &Message) -> Result<(), EngineError> {
+        writeln!(self.input, "{}", message)?;
+        self.input.flush()?;
+        Ok(())
+    }
+
+    /// Waits for answer, skipping lines which are not messages.
+    fn receive(&mut self, time: Duration) -> Result<Message, EngineError> {
+        let deadline = std::time::Instant::now() + time;
+        loop {
+            let left = deadline.saturating_duration_since(std::time::Instant::now());
+            match self.lines.recv_timeout(left) {
+                Ok(line) => match Message::parse(&line) {
+                    Ok(Message::Error(reason)) => return Err(EngineError::Engine(reason)),
+                    Ok(message) => return Ok(message),
+                    Err(_) => continue,
+                },
+                Err(RecvTimeoutError::Timeout) => return Err(EngineError::Timeout),
+                Err(RecvTimeoutError::Disconnected) => return Err(EngineError::Disconnected),
+            }
+        }
+    }
+
+    /// Waits till engine answered every question sent, dropping late answers.
+    fn ready(&mut self) -> Result<(), EngineError> {
+        self.send(&Message::IsReady)?;
+        self.readies += 1;
+        while self.readies > 0 {
+            match self.receive(GRACE * 5) {
+                Ok(Message::ReadyOk) => self.readies -= 1,
+                Ok(_) | Err(EngineError::Engine(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        self.stale = false;
+        Ok(())
+    }
+
+    /// Starts new game and waits till engine is ready.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`EngineError`] if engine does not get ready.
+    ///
+    /// [`EngineError`]: self::EngineError
+    pub fn new_game(
+        &mut self,
+        rows: usize,
+        cols: usize,
+        players: u8,
+        rules: Rules,
+    ) -> Result<(), EngineError> {
+        if self.stale {
+            self.ready()?;
+        }
+        self.send(&Message::NewGame(rows, cols, players, rules))?;
+        self.send(&Message::IsReady)?;
+        self.readies += 1;
+        match self.receive(GRACE * 5) {
+            Ok(Message::ReadyOk) => {
+                self.readies -= 1;
+                Ok(())
+            }
+            answer => {
+                self.stale = true;
+                Err(answer.map_or_else(|err| err, |m| EngineError::InvalidMessage(m.to_string())))
+            }
+        }
+    }
+
+    /// Asks engine for move after given turns.
+    ///
+    /// Moves of earlier questions which came too late are dropped first.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`EngineError`] if engine does not answer with a move in time.
+    ///
+    /// [`EngineError`]: self::EngineError
+    pub fn best_move(
+        &mut self,
+        turns: &[Turn],
+        time: Duration,
+    ) -> Result<(usize, usize), EngineError> {
+        if self.stale {
+            self.ready()?;
+        }
+        self.send(&Message::Position(turns.to_vec()))?;
+        self.send(&Message::Go(time))?;
+        match self.receive(time + GRACE) {
+            Ok(Message::BestMove(row, col)) => Ok((row, col)),
+            Err(EngineError::Engine(reason)) => Err(EngineError::Engine(reason)),
+            answer => {
+                self.stale = true;
+                Err(answer.map_or_else(|err| err, |m| EngineError::InvalidMessage(m.to_string())))
+            }
+        }
+    }
+}
+
+impl Drop for Host {
+    fn drop(&mut self) {
+        // Engine closes its output when it quits, otherwise it is stopped.
+        let quit = self.send(&Message::Quit).is_ok()
+            && self.receive(GRACE) == Err(EngineError::Disconnected);
+        if !quit {
+            let _ = self.child.kill();
+        }
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai;
+
+    #[test]
+    fn messages() {
+        for line in [
+            "newgame 4 5 3 wrap",
+            "isready",
+            "readyok",
+            "position",
+            "position 1,2 0,0",
+            "position 1,2 -1 0,0",
+            "go time 10",
+            "bestmove 0 9",
+            "error no move found",
+            "quit",
+        ] {
+            assert_eq!(Message::parse(line).unwrap().to_string(), line);
+        }
+        assert_eq!(
+            Message::parse("newgame 4 4 2"),
+            Ok(Message::NewGame(4, 4, 2, Rules::Classic))
+        );
+        for line in [
+            "newgame 4 4",
+            "position 1;2",
+            "position -x",
+            "go 10",
+            "bestmove 1",
+            "",
+        ] {
+            assert_eq!(
+                Message::parse(line),
+                Err(EngineError::InvalidMessage(line.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn engine_answers() {
+        let mut engine = Engine::new(ai::from_name("greedy", 0).unwrap());
+        let mut output = vec![];
+        let input = "go time 10\nnewgame 3 3 2\nhello\nposition 0,0 0,0\ngo time 10\n\
+                     position 0,0 2,2 0,0\ngo time 10\nisready\nquit\nisready\n";
+        engine.run(input.as_bytes(), &mut output).unwrap();
+        let lines: Vec<Message> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| Message::parse(line).unwrap())
+            .collect();
+        assert!(matches!(lines[0], Message::Error(_)));
+        assert!(matches!(lines[1], Message::Error(_)));
+        assert!(matches!(lines[2], Message::Error(_)));
+        assert!(matches!(lines[3], Message::BestMove(..)), "{:?}", lines[3]);
+        assert_eq!(lines[4..], [Message::ReadyOk]);
+    }
+
+    #[test]
+    fn new_game_limits() {
+        let mut engine = Engine::new(ai::from_name("random", 0).unwrap());
+        let go = Message::Go(Duration::from_millis(10));
+        for (rows, cols, players) in [
+            (2, 3, 2),
+            (3, MAX_SIZE + 1, 2),
+            (1_000_000, 1_000_000, 2),
+            (3, 3, 1),
+            (3, 3, MAX_PLAYERS + 1),
+        ] {
+            engine.handle(Message::NewGame(rows, cols, players, Rules::Classic));
+            let answer = engine.handle(go.clone());
+            assert!(matches!(answer, Some(Message::Error(_))), "{:?}", answer);
+        }
+        engine.handle(Message::NewGame(
+            MAX_SIZE,
+            MAX_SIZE,
+            MAX_PLAYERS,
+            Rules::Classic,
+        ));
+        assert!(matches!(engine.handle(go), Some(Message::BestMove(..))));
+    }
+}
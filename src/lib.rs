@@ -1,5 +1,15 @@
 #![warn(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+pub mod ai;
 pub mod board;
 mod cell;
+pub mod clock;
+pub mod engine;
+pub mod net;
+pub mod puzzle;
+pub mod record;
+mod rng;
+pub mod stats;
+pub mod tournament;
+mod websocket;
@@ -0,0 +1,1557 @@
+//! Provides authoritative host for games played over network.
+//!
+//! Clients and server exchange JSON messages, one per line. Clients join the
+//! lobby with a name, game starts once enough players have joined and
+//! players get ids in order of joining.
+//!
+//! ```text
+//! client: {"type":"join","name":"alice"}
+//! server: {"type":"lobby","names":["alice"],"players":2}
+//! server: {"type":"welcome","player":0,"token":"5f0c9e2a71d4b8363a8e0c7d19f2b45e"}
+//! server: {"type":"state","rows":10,"cols":10,"rules":"classic",...}
+//! client: {"type":"move","row":4,"col":4}
+//! server: {"type":"moved","player":0,"row":4,"col":4}
+//! server: {"type":"wave","wave":1,"exploding":[[0,0]],"cells":[[null,...],...]}
+//! server: {"type":"state",...}
+//! ```
+//!
+//! Player who lost connection gets the seat back by rejoining with token of
+//! welcome message within grace period. Seats of connected players cannot be
+//! taken over. Players who do not return in time
+//! forfeit, the game goes on without them.
+//!
+//! ```text
+//! client: {"type":"rejoin","token":"5f0c9e2a71d4b8363a8e0c7d19f2b45e"}
+//! ```
+//!
+//! Player may resign, leaving the game to others.
+//!
+//! ```text
+//! client: {"type":"resign"}
+//! server: {"type":"resigned","player":0}
+//! ```
+//!
+//! Players may also agree to a draw, game is drawn once every player in the
+//! game accepted. Offers stand in state until someone declines.
+//!
+//! ```text
+//! client: {"type":"offer_draw"}
+//! server: {"type":"draw_offered","player":0}
+//! server: {"type":"state",...,"draw_offers":[0]}
+//! client: {"type":"accept_draw"}
+//! server: {"type":"draw_accepted","player":1}
+//! server: {"type":"state",...,"drawn":true}
+//! ```
+//!
+//! Spectators watch a room, they get the same messages as players but
+//! cannot make moves.
+//!
+//! Server hosts [`Rooms`], joining without a code plays in the default room
+//! of the server. Client may create room with its own board instead and
+//! share the code it gets with other players.
+//!
+//! ```text
+//! client: {"type":"create","name":"alice","rows":8,"cols":8,"players":3,"rules":"wrap"}
+//! server: {"type":"room","code":"KXRW"}
+//! client: {"type":"join","name":"bob","room":"KXRW"}
+//! ```
+//!
+//! Browsers connect over WebSocket, where every message is one text message
+//! instead of a line.
+//!
+//! Games may be timed, see [`GameHost::with_time`]. State then has time
+//! each player has left in milliseconds, and players running out of time
+//! are dealt with as the time control says.
+//!
+//! ```text
+//! server: {"type":"state",...,"time_left":[8500,10000]}
+//! server: {"type":"timed_out","player":0,"action":"skip"}
+//! ```
+//!
+//! Every move is played on the host's [`Board`], so clients cannot make
+//! moves the rules do not allow. After explosions of a move each wave is
+//! sent, followed by state of settled board.
+//!
+//! [`Board`]: crate::board::Board
+
+use crate::board::{
+    Board, BoardState, CellState, LeavePolicy, MoveError, Rules, Standing, MAX_PLAYERS, MAX_SIZE,
+    MIN_SIZE,
+};
+use crate::clock::{GameClock, TimeControl, Timeout, TimeoutAction};
+use crate::rng::Rng;
+use crate::websocket;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
+
+/// Time player has to return after losing connection, unless set otherwise.
+pub const GRACE: Duration = Duration::from_secs(60);
+
+/// How often forfeits and timeouts are checked by [`serve`].
+const TICK: Duration = Duration::from_millis(500);
+
+/// How long [`serve`] waits for client to take message before dropping it.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Identifies connection of a client.
+pub type ClientId = usize;
+
+/// Rows of cells as owner and atoms, `None` for empty or exploding cells.
+pub type Cells = Vec<Vec<Option<(u8, u8)>>>;
+
+/// Message sent by client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Join lobby of next game.
+    Join {
+        /// Name shown to other players.
+        name: String,
+        /// Code of room, default room of server if not given.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        room: Option<String>,
+    },
+    /// Take back seat after losing connection.
+    Rejoin {
+        /// Token of welcome message.
+        token: String,
+    },
+    /// Watch games of room without playing.
+    Watch {
+        /// Code of room, default room of server if not given.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        room: Option<String>,
+    },
+    /// Create room and join its lobby.
+    Create {
+        /// Name shown to other players.
+        name: String,
+        /// Rows in board.
+        rows: usize,
+        /// Columns in board.
+        cols: usize,
+        /// Players needed for game.
+        players: u8,
+        /// Rules of board.
+        rules: Rules,
+    },
+    /// Place atom in cell.
+    Move {
+        /// Row of cell.
+        row: usize,
+        /// Column of cell.
+        col: usize,
+    },
+    /// Give up and leave the game.
+    Resign,
+    /// Offer other players a draw.
+    OfferDraw,
+    /// Agree to draw offered.
+    AcceptDraw,
+    /// Turn down draw offered.
+    DeclineDraw,
+}
+
+/// Game as seen by clients.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameState {
+    /// Rows in board.
+    pub rows: usize,
+    /// Columns in board.
+    pub cols: usize,
+    /// Rules of board.
+    pub rules: Rules,
+    /// Names of players by player id.
+    pub names: Vec<String>,
+    /// Cells of board.
+    pub cells: Cells,
+    /// Player whose turn it is.
+    pub turn: u8,
+    /// Players still in the game.
+    pub alive: Vec<bool>,
+    /// Winner once game is over.
+    pub winner: Option<u8>,
+    /// Whether game ended in a draw.
+    #[serde(default)]
+    pub drawn: bool,
+    /// Players agreeing to a draw.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub draw_offers: Vec<u8>,
+    /// Ranking of players once game is over.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub standings: Vec<Standing>,
+    /// Milliseconds each player has left for the turn, in timed games.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_left: Option<Vec<u64>>,
+}
+
+impl GameState {
+    /// Create state of board with names of players.
+    pub fn new(board: &Board, names: Vec<String>) -> Self {
+        Self {
+            rows: board.rows(),
+            cols: board.cols(),
+            rules: board.rules(),
+            names,
+            cells: cells(board),
+            turn: board.current_player_id(),
+            alive: (0..board.player_count())
+                .map(|player| board.is_player_alive(player))
+                .collect(),
+            winner: match board.state() {
+                BoardState::GameOver(winner) => Some(*winner),
+                _ => None,
+            },
+            drawn: board.state() == &BoardState::Draw,
+            draw_offers: board.draw_offers(),
+            standings: if board.is_over() {
+                board.standings()
+            } else {
+                vec![]
+            },
+            time_left: None,
+        }
+    }
+
+    /// Whether game is over, won by a player or drawn.
+    pub fn is_over(&self) -> bool {
+        self.winner.is_some() || self.drawn
+    }
+
+    /// Create state of timed game with names of players.
+    pub fn timed(clock: &GameClock, names: Vec<String>, now: Instant) -> Self {
+        let board = clock.board();
+        let time_left = (0..board.player_count())
+            .map(|player| clock.time_left(player, now))
+            .map(|left| left.map(|left| left.as_millis() as u64))
+            .collect();
+        Self {
+            time_left,
+            ..Self::new(board, names)
+        }
+    }
+}
+
+/// Message sent by server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// Players waiting for game to start.
+    Lobby {
+        /// Names of players in lobby.
+        names: Vec<String>,
+        /// Players needed for game.
+        players: u8,
+    },
+    /// Room was created, other players join it with the code.
+    Room {
+        /// Code of room.
+        code: String,
+    },
+    /// Game started, the player id of client is stored.
+    Welcome {
+        /// Player id of client.
+        player: u8,
+        /// Secret for rejoining after losing connection.
+        token: String,
+    },
+    /// Board after move is over.
+    State(GameState),
+    /// Move was accepted.
+    Moved {
+        /// Player who made the move.
+        player: u8,
+        /// Row of cell.
+        row: usize,
+        /// Column of cell.
+        col: usize,
+    },
+    /// Explosion wave of last move.
+    Wave {
+        /// Number of wave, starting from 1.
+        wave: usize,
+        /// Cells exploding in this wave.
+        exploding: Vec<(usize, usize)>,
+        /// Cells of board during this wave.
+        cells: Cells,
+    },
+    /// Player lost connection, the seat is kept for grace period.
+    Left {
+        /// Player who left.
+        player: u8,
+    },
+    /// Player gave up and is out of the game.
+    Resigned {
+        /// Player who resigned.
+        player: u8,
+    },
+    /// Player offered a draw.
+    DrawOffered {
+        /// Player who offered.
+        player: u8,
+    },
+    /// Player agreed to draw offered.
+    DrawAccepted {
+        /// Player who accepted.
+        player: u8,
+    },
+    /// Player turned down draw offered.
+    DrawDeclined {
+        /// Player who declined.
+        player: u8,
+    },
+    /// Player did not return in time and is out of the game.
+    Forfeited {
+        /// Player who forfeited.
+        player: u8,
+    },
+    /// Player ran out of time for the move.
+    TimedOut {
+        /// Player who ran out of time.
+        player: u8,
+        /// What was done instead of move of player.
+        action: TimeoutAction,
+    },
+    /// Player who lost connection joined again.
+    Returned {
+        /// Player who returned.
+        player: u8,
+    },
+    /// Message could not be handled.
+    Error {
+        /// Reason message failed.
+        message: String,
+    },
+}
+
+/// Provides cells of board for messages.
+pub fn cells(board: &Board) -> Cells {
+    board
+        .cells()
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|cell| match cell {
+                    CellState::NonEmpty(owner, atoms) => Some((owner, atoms)),
+                    CellState::Empty | CellState::Explosion => None,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Provides cells of message for rendering, with exploding cells marked.
+///
+/// # Examples
+///
+/// ```
+/// use chain_reaction::board::CellState;
+/// use chain_reaction::net::cell_states;
+///
+/// let cells = vec![vec![None, Some((1, 2))]];
+/// assert_eq!(
+///     cell_states(&cells, &[(0, 0)]),
+///     vec![vec![CellState::Explosion, CellState::NonEmpty(1, 2)]]
+/// );
+/// ```
+pub fn cell_states(cells: &Cells, exploding: &[(usize, usize)]) -> Vec<Vec<CellState>> {
+    cells
+        .iter()
+        .enumerate()
+        .map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .map(|(col, cell)| match *cell {
+                    _ if exploding.contains(&(row, col)) => CellState::Explosion,
+                    Some((owner, atoms)) => CellState::NonEmpty(owner, atoms),
+                    None => CellState::Empty,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Host of game deciding outcome of moves sent by clients.
+///
+/// Host does not do any networking, it provides messages to be sent to each
+/// client.
+///
+/// # Examples
+///
+/// ```
+/// use chain_reaction::board::Rules;
+/// use chain_reaction::net::{ClientMessage, GameHost, ServerMessage};
+///
+/// let mut host = GameHost::new(3, 3, 2, Rules::Classic);
+/// let join = |name: &str| ClientMessage::Join {
+///     name: name.to_string(),
+///     room: None,
+/// };
+/// host.handle(7, join("alice"));
+/// let sent = host.handle(9, join("bob"));
+/// assert!(sent
+///     .iter()
+///     .any(|sent| matches!(sent, (9, ServerMessage::Welcome { player: 1, .. }))));
+/// let sent = host.handle(9, ClientMessage::Move { row: 0, col: 0 });
+/// assert!(matches!(sent[..], [(9, ServerMessage::Error { .. })]));
+/// ```
+pub struct GameHost {
+    rows: usize,
+    cols: usize,
+    players: u8,
+    rules: Rules,
+    grace: Duration,
+    time: TimeControl,
+    leave: LeavePolicy,
+    lobby: Vec<(ClientId, String)>,
+    seats: Vec<Seat>,
+    spectators: Vec<ClientId>,
+    game: Option<GameClock>,
+    rng: Rng,
+}
+
+/// Player of game in progress.
+struct Seat {
+    name: String,
+    token: String,
+    /// Connection of player, `None` while player is away.
+    client: Option<ClientId>,
+    /// When player lost connection.
+    left: Option<Instant>,
+}
+
+/// Provides secret for rejoining, drawn from randomness of the operating
+/// system so tokens tell nothing about each other or about the game.
+fn new_token() -> String {
+    let mut bytes = [0; 16];
+    getrandom::getrandom(&mut bytes).expect("operating system provides randomness");
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn random_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |time| time.as_nanos() as u64)
+}
+
+impl GameHost {
+    /// Create host for games with given board and players.
+    pub fn new(rows: usize, cols: usize, players: u8, rules: Rules) -> Self {
+        Self {
+            rows,
+            cols,
+            players,
+            rules,
+            grace: GRACE,
+            time: TimeControl::default(),
+            leave: LeavePolicy::default(),
+            lobby: vec![],
+            seats: vec![],
+            spectators: vec![],
+            game: None,
+            rng: Rng::new(random_seed()),
+        }
+    }
+
+    /// Set time players have to return after losing connection.
+    pub fn with_grace(mut self, grace: Duration) -> Self {
+        self.grace = grace;
+        self
+    }
+
+    /// Set time limits of games, untimed by default.
+    pub fn with_time(mut self, time: TimeControl) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// Set what happens to atoms of players leaving games.
+    pub fn with_leave_policy(mut self, leave: LeavePolicy) -> Self {
+        self.leave = leave;
+        self
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.seats.iter().map(|seat| seat.name.clone()).collect()
+    }
+
+    fn player(&self, client: ClientId) -> Option<u8> {
+        self.seats
+            .iter()
+            .position(|seat| seat.client == Some(client))
+            .map(|player| player as u8)
+    }
+
+    /// Provides message for every connected player and spectator.
+    fn broadcast(&self, message: ServerMessage) -> Vec<(ClientId, ServerMessage)> {
+        self.seats
+            .iter()
+            .filter_map(|seat| seat.client)
+            .chain(self.spectators.iter().copied())
+            .map(|client| (client, message.clone()))
+            .collect()
+    }
+
+    fn lobby(&self) -> Vec<(ClientId, ServerMessage)> {
+        let message = ServerMessage::Lobby {
+            names: self.lobby.iter().map(|(_, name)| name.clone()).collect(),
+            players: self.players,
+        };
+        self.lobby
+            .iter()
+            .map(|(client, _)| *client)
+            .chain(self.spectators.iter().copied())
+            .map(|client| (client, message.clone()))
+            .collect()
+    }
+
+    fn state(&self, game: &GameClock, now: Instant) -> ServerMessage {
+        if game.control().is_timed() {
+            ServerMessage::State(GameState::timed(game, self.names(), now))
+        } else {
+            ServerMessage::State(GameState::new(game.board(), self.names()))
+        }
+    }
+
+    fn join(&mut self, client: ClientId, name: String) -> Vec<(ClientId, ServerMessage)> {
+        let error = |message: &str| {
+            vec![(
+                client,
+                ServerMessage::Error {
+                    message: message.to_string(),
+                },
+            )]
+        };
+        if self.has_client(client) {
+            return error("already joined");
+        }
+        if name.trim().is_empty() {
+            return error("name is empty");
+        }
+        if self.game.is_some() {
+            return error("game in progress");
+        }
+        self.lobby.push((client, name.trim().to_string()));
+        let mut sent = self.lobby();
+        if self.lobby.len() == self.players as usize {
+            let lobby: Vec<_> = self.lobby.drain(..).collect();
+            self.seats = lobby
+                .into_iter()
+                .map(|(client, name)| Seat {
+                    name,
+                    token: new_token(),
+                    client: Some(client),
+                    left: None,
+                })
+                .collect();
+            let board = Board::with_rules(self.rows, self.cols, self.players, self.rules)
+                .with_leave_policy(self.leave);
+            let now = Instant::now();
+            let game = GameClock::new(board, self.time, now).with_seed(self.rng.next_u64());
+            for (player, seat) in self.seats.iter().enumerate() {
+                if let Some(client) = seat.client {
+                    let player = player as u8;
+                    let token = seat.token.clone();
+                    sent.push((client, ServerMessage::Welcome { player, token }));
+                }
+            }
+            sent.extend(self.broadcast(self.state(&game, now)));
+            self.game = Some(game);
+        }
+        sent
+    }
+
+    fn rejoin(&mut self, client: ClientId, token: &str) -> Vec<(ClientId, ServerMessage)> {
+        let error = |message: &str| {
+            vec![(
+                client,
+                ServerMessage::Error {
+                    message: message.to_string(),
+                },
+            )]
+        };
+        let seat = self.seats.iter().position(|seat| seat.token == token);
+        let (Some(player), Some(game)) = (seat, self.game.as_ref()) else {
+            return error("unknown token");
+        };
+        if self.seats[player].left.is_none() {
+            return error("seat is still connected");
+        }
+        let state = self.state(game, Instant::now());
+        let player = player as u8;
+        let mut sent = self.broadcast(ServerMessage::Returned { player });
+        let seat = &mut self.seats[player as usize];
+        seat.client = Some(client);
+        seat.left = None;
+        let token = seat.token.clone();
+        sent.push((client, ServerMessage::Welcome { player, token }));
+        sent.push((client, state));
+        sent
+    }
+
+    fn watch(&mut self, client: ClientId) -> Vec<(ClientId, ServerMessage)> {
+        if self.has_client(client) {
+            return vec![(
+                client,
+                ServerMessage::Error {
+                    message: "already joined".to_string(),
+                },
+            )];
+        }
+        self.spectators.push(client);
+        match self.game {
+            Some(ref game) => vec![(client, self.state(game, Instant::now()))],
+            None => self.lobby(),
+        }
+    }
+
+    /// Provides messages for board after change, ending game if it is over.
+    fn finish_turn(&mut self, game: GameClock, now: Instant) -> Vec<(ClientId, ServerMessage)> {
+        let sent = self.broadcast(self.state(&game, now));
+        if game.board().is_over() {
+            // Players may join again for next game.
+            self.seats.clear();
+        } else {
+            self.game = Some(game);
+        }
+        sent
+    }
+
+    /// Provides messages for move made, with its explosion waves.
+    fn moved(
+        &mut self,
+        mut game: GameClock,
+        (player, row, col): (u8, usize, usize),
+        now: Instant,
+    ) -> Vec<(ClientId, ServerMessage)> {
+        let mut sent = self.broadcast(ServerMessage::Moved { player, row, col });
+        let mut wave = 0;
+        while let BoardState::Explosion(exploding) = game.board().state() {
+            wave += 1;
+            sent.extend(self.broadcast(ServerMessage::Wave {
+                wave,
+                exploding: exploding.clone(),
+                cells: cells(game.board()),
+            }));
+            game.next_iteration(now);
+        }
+        game.settle(now);
+        sent.extend(self.finish_turn(game, now));
+        sent
+    }
+
+    fn play(&mut self, client: ClientId, row: usize, col: usize) -> Vec<(ClientId, ServerMessage)> {
+        let error = |message: String| vec![(client, ServerMessage::Error { message })];
+        if self.spectators.contains(&client) {
+            return error("spectators cannot make moves".to_string());
+        }
+        let (Some(player), Some(mut game)) = (self.player(client), self.game.take()) else {
+            return error("not in game".to_string());
+        };
+        let now = Instant::now();
+        if let Err(err) = game.player_move(player, row, col, now) {
+            self.game = Some(game);
+            return error(format!("{:?}", err));
+        }
+        self.moved(game, (player, row, col), now)
+    }
+
+    /// Lets player of client resign or answer draws, telling everyone
+    /// what was done.
+    fn act(
+        &mut self,
+        client: ClientId,
+        action: fn(&mut GameClock, u8, Instant) -> Result<(), MoveError>,
+        done: fn(u8) -> ServerMessage,
+    ) -> Vec<(ClientId, ServerMessage)> {
+        let error = |message: String| vec![(client, ServerMessage::Error { message })];
+        let (Some(player), Some(mut game)) = (self.player(client), self.game.take()) else {
+            return error("not in game".to_string());
+        };
+        let now = Instant::now();
+        if let Err(err) = action(&mut game, player, now) {
+            self.game = Some(game);
+            return error(format!("{:?}", err));
+        }
+        let mut sent = self.broadcast(done(player));
+        sent.extend(self.finish_turn(game, now));
+        sent
+    }
+
+    /// Handles message of client, provides messages to be sent.
+    pub fn handle(
+        &mut self,
+        client: ClientId,
+        message: ClientMessage,
+    ) -> Vec<(ClientId, ServerMessage)> {
+        match message {
+            ClientMessage::Join { name, .. } => self.join(client, name),
+            ClientMessage::Rejoin { token } => self.rejoin(client, &token),
+            ClientMessage::Watch { .. } => self.watch(client),
+            ClientMessage::Move { row, col } => self.play(client, row, col),
+            ClientMessage::Resign => self.act(client, GameClock::resign, |player| {
+                ServerMessage::Resigned { player }
+            }),
+            ClientMessage::OfferDraw => self.act(client, GameClock::offer_draw, |player| {
+                ServerMessage::DrawOffered { player }
+            }),
+            ClientMessage::AcceptDraw => self.act(client, GameClock::accept_draw, |player| {
+                ServerMessage::DrawAccepted { player }
+            }),
+            ClientMessage::DeclineDraw => self.act(
+                client,
+                |game, player, _| game.decline_draw(player),
+                |player| ServerMessage::DrawDeclined { player },
+            ),
+            ClientMessage::Create { .. } => vec![(
+                client,
+                ServerMessage::Error {
+                    message: "rooms cannot be created here".to_string(),
+                },
+            )],
+        }
+    }
+
+    /// Whether client is in lobby, game or watching.
+    fn has_client(&self, client: ClientId) -> bool {
+        self.player(client).is_some()
+            || self.lobby.iter().any(|(c, _)| *c == client)
+            || self.spectators.contains(&client)
+    }
+
+    /// Whether token belongs to seat of game in progress.
+    fn has_token(&self, token: &str) -> bool {
+        self.game.is_some() && self.seats.iter().any(|seat| seat.token == token)
+    }
+
+    /// Whether no client is in lobby or watching and no game is in progress.
+    fn is_empty(&self) -> bool {
+        self.lobby.is_empty() && self.spectators.is_empty() && self.game.is_none()
+    }
+
+    /// Handles lost connection of client, provides messages to be sent.
+    ///
+    /// Player keeps the seat till grace period is over, see [`GameHost::expire`].
+    pub fn disconnect(&mut self, client: ClientId) -> Vec<(ClientId, ServerMessage)> {
+        self.spectators.retain(|c| *c != client);
+        if let Some(index) = self.lobby.iter().position(|(c, _)| *c == client) {
+            self.lobby.remove(index);
+            return self.lobby();
+        }
+        match self.player(client) {
+            Some(player) => {
+                let seat = &mut self.seats[player as usize];
+                seat.client = None;
+                seat.left = Some(Instant::now());
+                self.broadcast(ServerMessage::Left { player })
+            }
+            None => vec![],
+        }
+    }
+
+    /// Forfeits players whose grace period is over and deals with player
+    /// out of time, provides messages to be sent.
+    pub fn expire(&mut self, now: Instant) -> Vec<(ClientId, ServerMessage)> {
+        let mut sent = vec![];
+        for player in 0..self.seats.len() {
+            let Some(mut game) = self.game.take() else {
+                break;
+            };
+            let seat = &mut self.seats[player];
+            let expired = matches!(seat.left, Some(left) if now.duration_since(left) >= self.grace);
+            let player = player as u8;
+            if !expired || game.forfeit(player, now).is_err() {
+                self.game = Some(game);
+                continue;
+            }
+            seat.left = None;
+            sent.extend(self.broadcast(ServerMessage::Forfeited { player }));
+            sent.extend(self.finish_turn(game, now));
+        }
+        let Some(mut game) = self.game.take() else {
+            return sent;
+        };
+        let Some(timeout) = game.tick(now) else {
+            self.game = Some(game);
+            return sent;
+        };
+        let (player, action) = match timeout {
+            Timeout::Forfeited(player) => (player, TimeoutAction::Forfeit),
+            Timeout::Moved { player, .. } => (player, TimeoutAction::RandomMove),
+            Timeout::Skipped(player) => (player, TimeoutAction::Skip),
+        };
+        sent.extend(self.broadcast(ServerMessage::TimedOut { player, action }));
+        match timeout {
+            Timeout::Moved { player, row, col } => {
+                sent.extend(self.moved(game, (player, row, col), now))
+            }
+            _ => sent.extend(self.finish_turn(game, now)),
+        }
+        sent
+    }
+}
+
+/// Letters of room codes, without ones easily mistaken for others.
+const CODE_LETTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+const CODE_LEN: usize = 4;
+
+/// Rooms each hosting own game, with default room for clients not giving code.
+///
+/// # Examples
+///
+/// ```
+/// use chain_reaction::board::Rules;
+/// use chain_reaction::net::{ClientMessage, Rooms, ServerMessage};
+///
+/// let mut rooms = Rooms::new(3, 3, 2, Rules::Classic);
+/// let create = ClientMessage::Create {
+///     name: "alice".to_string(),
+///     rows: 5,
+///     cols: 5,
+///     players: 2,
+///     rules: Rules::Wrap,
+/// };
+/// let ServerMessage::Room { code } = rooms.handle(1, create).remove(0).1 else {
+///     panic!("room expected");
+/// };
+/// let join = ClientMessage::Join { name: "bob".to_string(), room: Some(code) };
+/// let sent = rooms.handle(2, join);
+/// assert!(sent
+///     .iter()
+///     .any(|sent| matches!(sent, (2, ServerMessage::Welcome { player: 1, .. }))));
+/// ```
+pub struct Rooms {
+    hosts: HashMap<String, GameHost>,
+    clients: HashMap<ClientId, String>,
+    grace: Duration,
+    time: TimeControl,
+    leave: LeavePolicy,
+    rng: Rng,
+}
+
+impl Rooms {
+    /// Code of room joined without code.
+    const DEFAULT: &'static str = "";
+
+    /// Create rooms with default room having given board and players.
+    pub fn new(rows: usize, cols: usize, players: u8, rules: Rules) -> Self {
+        let mut hosts = HashMap::new();
+        hosts.insert(
+            Self::DEFAULT.to_string(),
+            GameHost::new(rows, cols, players, rules),
+        );
+        Self {
+            hosts,
+            clients: HashMap::new(),
+            grace: GRACE,
+            time: TimeControl::default(),
+            leave: LeavePolicy::default(),
+            rng: Rng::new(random_seed()),
+        }
+    }
+
+    /// Set time players have to return after losing connection, in every room.
+    pub fn with_grace(mut self, grace: Duration) -> Self {
+        self.grace = grace;
+        self.hosts = self
+            .hosts
+            .into_iter()
+            .map(|(code, host)| (code, host.with_grace(grace)))
+            .collect();
+        self
+    }
+
+    /// Set time limits of games, in every room.
+    pub fn with_time(mut self, time: TimeControl) -> Self {
+        self.time = time;
+        self.hosts = self
+            .hosts
+            .into_iter()
+            .map(|(code, host)| (code, host.with_time(time)))
+            .collect();
+        self
+    }
+
+    /// Set what happens to atoms of players leaving games, in every room.
+    pub fn with_leave_policy(mut self, leave: LeavePolicy) -> Self {
+        self.leave = leave;
+        self.hosts = self
+            .hosts
+            .into_iter()
+            .map(|(code, host)| (code, host.with_leave_policy(leave)))
+            .collect();
+        self
+    }
+
+    fn new_code(&mut self) -> String {
+        loop {
+            let code: String = (0..CODE_LEN)
+                .map(|_| CODE_LETTERS[self.rng.below(CODE_LETTERS.len())] as char)
+                .collect();
+            if !self.hosts.contains_key(&code) {
+                return code;
+            }
+        }
+    }
+
+    /// Provides room client is in, forgetting rooms client is done with.
+    fn room(&mut self, client: ClientId) -> Option<&mut GameHost> {
+        let code = self.clients.get(&client)?.clone();
+        match self.hosts.get(&code) {
+            Some(host) if host.has_client(client) => self.hosts.get_mut(&code),
+            _ => {
+                self.clients.remove(&client);
+                None
+            }
+        }
+    }
+
+    /// Removes rooms nobody uses, except default room.
+    fn remove_empty(&mut self) {
+        self.hosts
+            .retain(|code, host| code == Self::DEFAULT || !host.is_empty());
+    }
+
+    /// Handles message of client, provides messages to be sent.
+    pub fn handle(
+        &mut self,
+        client: ClientId,
+        message: ClientMessage,
+    ) -> Vec<(ClientId, ServerMessage)> {
+        let error = |message: &str| {
+            vec![(
+                client,
+                ServerMessage::Error {
+                    message: message.to_string(),
+                },
+            )]
+        };
+        if let Some(host) = self.room(client) {
+            return match message {
+                ClientMessage::Create { .. } => error("already joined"),
+                message => host.handle(client, message),
+            };
+        }
+        let mut sent = vec![];
+        let code = match message {
+            ClientMessage::Join { ref room, .. } | ClientMessage::Watch { ref room } => {
+                room.clone().unwrap_or_default()
+            }
+            ClientMessage::Rejoin { ref token } => {
+                match self.hosts.iter().find(|(_, host)| host.has_token(token)) {
+                    Some((code, _)) => code.clone(),
+                    None => return error("unknown token"),
+                }
+            }
+            ClientMessage::Create {
+                rows,
+                cols,
+                players,
+                rules,
+                ..
+            } => {
+                let sizes = MIN_SIZE..=MAX_SIZE;
+                if !sizes.contains(&rows) || !sizes.contains(&cols) {
+                    return error(&format!(
+                        "board needs {} to {} rows and columns",
+                        MIN_SIZE, MAX_SIZE
+                    ));
+                }
+                if !(2..=MAX_PLAYERS).contains(&players) {
+                    return error(&format!("players should be between 2 and {}", MAX_PLAYERS));
+                }
+                let code = self.new_code();
+                let host = GameHost::new(rows, cols, players, rules)
+                    .with_grace(self.grace)
+                    .with_time(self.time)
+                    .with_leave_policy(self.leave);
+                self.hosts.insert(code.clone(), host);
+                sent.push((client, ServerMessage::Room { code: code.clone() }));
+                code
+            }
+            ClientMessage::Move { .. }
+            | ClientMessage::Resign
+            | ClientMessage::OfferDraw
+            | ClientMessage::AcceptDraw
+            | ClientMessage::DeclineDraw => return error("not in game"),
+        };
+        let Some(host) = self.hosts.get_mut(&code) else {
+            return error("no such room");
+        };
+        let message = match message {
+            ClientMessage::Create { name, .. } => ClientMessage::Join { name, room: None },
+            message => message,
+        };
+        sent.extend(host.handle(client, message));
+        if host.has_client(client) {
+            self.clients.insert(client, code);
+        }
+        self.remove_empty();
+        sent
+    }
+
+    /// Handles lost connection of client, provides messages to be sent.
+    pub fn disconnect(&mut self, client: ClientId) -> Vec<(ClientId, ServerMessage)> {
+        let Some(code) = self.clients.remove(&client) else {
+            return vec![];
+        };
+        let sent = match self.hosts.get_mut(&code) {
+            Some(host) => host.disconnect(client),
+            None => vec![],
+        };
+        self.remove_empty();
+        sent
+    }
+
+    /// Forfeits players whose grace period is over, provides messages to be sent.
+    pub fn expire(&mut self, now: Instant) -> Vec<(ClientId, ServerMessage)> {
+        let sent = self
+            .hosts
+            .values_mut()
+            .flat_map(|host| host.expire(now))
+            .collect();
+        self.remove_empty();
+        sent
+    }
+}
+
+/// How messages are exchanged over connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    /// JSON message on each line.
+    Lines,
+    /// JSON message in each WebSocket text message, for browsers.
+    WebSocket,
+}
+
+/// Writing side of connection.
+struct Writer {
+    transport: Transport,
+    stream: TcpStream,
+}
+
+impl Writer {
+    fn send(&mut self, message: &ServerMessage) -> std::io::Result<()> {
+        let text = serde_json::to_string(message).expect("messages are serializable");
+        match self.transport {
+            Transport::Lines => writeln!(self.stream, "{}", text),
+            Transport::WebSocket => websocket::write_message(&mut self.stream, &text),
+        }
+    }
+}
+
+enum Event {
+    Connected(ClientId, Writer),
+    Message(ClientId, String),
+    Closed(ClientId),
+}
+
+/// Reads line of client, `None` once connection is closed.
+///
+/// Lines are as long as WebSocket messages may be at most, so clients
+/// cannot run server out of memory.
+fn read_line(reader: &mut impl BufRead) -> std::io::Result<Option<String>> {
+    let mut line = String::new();
+    let limit = websocket::MAX_MESSAGE as u64 + 1;
+    match reader.by_ref().take(limit).read_line(&mut line)? {
+        0 => Ok(None),
+        len if len as u64 == limit && !line.ends_with('\n') => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "line too long",
+        )),
+        _ => Ok(Some(line)),
+    }
+}
+
+/// Reads messages of connection till it is closed.
+fn read_messages(
+    client: ClientId,
+    transport: Transport,
+    stream: TcpStream,
+    events: Sender<Event>,
+) -> std::io::Result<()> {
+    let mut writer = Writer {
+        transport,
+        stream: stream.try_clone()?,
+    };
+    writer.stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+    if transport == Transport::WebSocket {
+        websocket::accept(&mut reader, &mut writer.stream)?;
+    }
+    if events.send(Event::Connected(client, writer)).is_err() {
+        return Ok(());
+    }
+    loop {
+        let message = match transport {
+            Transport::Lines => read_line(&mut reader)?,
+            Transport::WebSocket => websocket::read_message(&mut reader)?,
+        };
+        let Some(message) = message else {
+            return Ok(());
+        };
+        if events.send(Event::Message(client, message)).is_err() {
+            return Ok(());
+        }
+    }
+}
+
+/// Hosts rooms for clients connecting to listeners, runs forever.
+///
+/// Each connection is read on its own thread, while all messages are
+/// handled one by one by the rooms. Forfeits are checked in between.
+/// Clients not taking their messages in time are dropped, so they cannot
+/// hold up everyone else.
+pub fn serve(listeners: Vec<(TcpListener, Transport)>, mut rooms: Rooms) {
+    let (sender, events) = mpsc::channel();
+    let clients = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    for (listener, transport) in listeners {
+        let sender = sender.clone();
+        let clients = clients.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                let client = clients.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                let events = sender.clone();
+                std::thread::spawn(move || {
+                    // Failed handshake or connection closes it the same way.
+                    let _ = read_messages(client, transport, stream, events.clone());
+                    let _ = events.send(Event::Closed(client));
+                });
+            }
+        });
+    }
+    drop(sender);
+    let mut writers: HashMap<ClientId, Writer> = HashMap::new();
+    loop {
+        let mut sent = match events.recv_timeout(TICK) {
+            Err(RecvTimeoutError::Disconnected) => return,
+            Err(RecvTimeoutError::Timeout) => vec![],
+            Ok(Event::Connected(client, writer)) => {
+                writers.insert(client, writer);
+                vec![]
+            }
+            Ok(Event::Message(_, message)) if message.trim().is_empty() => vec![],
+            Ok(Event::Message(client, message)) => match serde_json::from_str(&message) {
+                Ok(message) => rooms.handle(client, message),
+                Err(err) => vec![(
+                    client,
+                    ServerMessage::Error {
+                        message: format!("invalid message: {}", err),
+                    },
+                )],
+            },
+            Ok(Event::Closed(client)) => {
+                writers.remove(&client);
+                rooms.disconnect(client)
+            }
+        };
+        sent.extend(rooms.expire(Instant::now()));
+        while !sent.is_empty() {
+            let mut dropped = vec![];
+            for (client, message) in sent {
+                let Some(writer) = writers.get_mut(&client) else {
+                    continue;
+                };
+                if writer.send(&message).is_err() {
+                    // Shutdown stops its reader too, Closed is then ignored.
+                    let _ = writer.stream.shutdown(std::net::Shutdown::Both);
+                    writers.remove(&client);
+                    dropped.push(client);
+                }
+            }
+            sent = dropped
+                .into_iter()
+                .flat_map(|client| rooms.disconnect(client))
+                .collect();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn join(name: &str) -> ClientMessage {
+        ClientMessage::Join {
+            name: name.to_string(),
+            room: None,
+        }
+    }
+
+    /// Provides player id and token welcoming client.
+    fn welcome(sent: &[(ClientId, ServerMessage)], client: ClientId) -> (u8, String) {
+        sent.iter()
+            .find_map(|sent| match sent {
+                (c, ServerMessage::Welcome { player, token }) if *c == client => {
+                    Some((*player, token.clone()))
+                }
+                _ => None,
+            })
+            .expect("client is welcomed")
+    }
+
+    #[test]
+    fn json_messages() {
+        let message: ClientMessage =
+            serde_json::from_str(r#"{"type":"move","row":1,"col":2}"#).unwrap();
+        assert_eq!(message, ClientMessage::Move { row: 1, col: 2 });
+        let message = ServerMessage::Wave {
+            wave: 1,
+            exploding: vec![(0, 0)],
+            cells: vec![vec![None, Some((1, 2))]],
+        };
+        assert_eq!(
+            serde_json::to_string(&message).unwrap(),
+            r#"{"type":"wave","wave":1,"exploding":[[0,0]],"cells":[[null,[1,2]]]}"#
+        );
+        let state = GameState::new(&Board::new(3, 3, 2), vec![]);
+        let json = serde_json::to_string(&ServerMessage::State(state.clone())).unwrap();
+        assert!(json.starts_with(r#"{"type":"state","rows":3,"cols":3,"rules":"classic""#));
+        assert_eq!(
+            serde_json::from_str::<ServerMessage>(&json).unwrap(),
+            ServerMessage::State(state)
+        );
+    }
+
+    #[test]
+    fn lobby_and_moves() {
+        let mut host = GameHost::new(3, 3, 2, Rules::Classic);
+        assert!(matches!(
+            host.handle(1, ClientMessage::Move { row: 0, col: 0 })[..],
+            [(1, ServerMessage::Error { .. })]
+        ));
+        host.handle(2, join("carol"));
+        assert_eq!(host.disconnect(2), vec![]);
+        assert_eq!(
+            host.handle(1, join("alice")),
+            vec![(
+                1,
+                ServerMessage::Lobby {
+                    names: vec!["alice".to_string()],
+                    players: 2
+                }
+            )]
+        );
+        assert!(matches!(
+            host.handle(1, join("alice"))[..],
+            [(1, ServerMessage::Error { .. })]
+        ));
+        let sent = host.handle(3, join("bob"));
+        let (player, token) = welcome(&sent, 1);
+        assert_eq!(player, 0);
+        let (bob, bob_token) = welcome(&sent, 3);
+        assert_eq!(bob, 1);
+        assert!(matches!(
+            host.handle(4, join("dave"))[..],
+            [(4, ServerMessage::Error { .. })]
+        ));
+        // Moves are checked by board.
+        assert!(matches!(
+            host.handle(3, ClientMessage::Move { row: 0, col: 0 })[..],
+            [(3, ServerMessage::Error { .. })]
+        ));
+        host.handle(1, ClientMessage::Move { row: 0, col: 0 });
+        host.handle(3, ClientMessage::Move { row: 2, col: 2 });
+        let sent = host.handle(1, ClientMessage::Move { row: 0, col: 0 });
+        let waves = sent
+            .iter()
+            .filter(|(client, message)| {
+                *client == 3 && matches!(message, ServerMessage::Wave { .. })
+            })
+            .count();
+        assert_eq!(waves, 1);
+        // Token of connected player does not take their seat.
+        assert!(matches!(
+            host.handle(6, ClientMessage::Rejoin { token: bob_token })[..],
+            [(6, ServerMessage::Error { .. })]
+        ));
+        assert_eq!(
+            host.disconnect(1),
+            vec![(3, ServerMessage::Left { player: 0 })]
+        );
+        // Seat is taken back with token, not name.
+        assert!(matches!(
+            host.handle(5, join("alice"))[..],
+            [(5, ServerMessage::Error { .. })]
+        ));
+        let rejoin = |token: &str| ClientMessage::Rejoin {
+            token: token.to_string(),
+        };
+        assert!(matches!(
+            host.handle(5, rejoin("0000"))[..],
+            [(5, ServerMessage::Error { .. })]
+        ));
+        let sent = host.handle(5, rejoin(&token));
+        assert_eq!(sent[0], (3, ServerMessage::Returned { player: 0 }));
+        assert_eq!(welcome(&sent, 5), (0, token));
+        assert!(matches!(sent[2], (5, ServerMessage::State(_))));
+        // Returned player is not forfeited.
+        assert_eq!(host.expire(Instant::now() + GRACE), vec![]);
+    }
+
+    #[test]
+    fn spectators_and_forfeits() {
+        let mut host = GameHost::new(3, 3, 3, Rules::Classic).with_grace(Duration::from_secs(5));
+        let watch = ClientMessage::Watch { room: None };
+        host.handle(9, watch.clone());
+        host.handle(1, join("alice"));
+        host.handle(2, join("bob"));
+        let sent = host.handle(3, join("carol"));
+        // Spectator gets the same messages as players, without welcome.
+        let to_spectator: Vec<_> = sent.iter().filter(|(client, _)| *client == 9).collect();
+        assert!(matches!(
+            to_spectator[..],
+            [
+                (_, ServerMessage::Lobby { .. }),
+                (_, ServerMessage::State(_))
+            ]
+        ));
+        assert!(matches!(
+            host.handle(9, ClientMessage::Move { row: 0, col: 0 })[..],
+            [(9, ServerMessage::Error { .. })]
+        ));
+        assert!(matches!(
+            host.handle(8, watch)[..],
+            [(8, ServerMessage::State(_))]
+        ));
+        host.handle(1, ClientMessage::Move { row: 0, col: 0 });
+        host.disconnect(2);
+        let left = Instant::now();
+        assert_eq!(host.expire(left + Duration::from_secs(1)), vec![]);
+        let sent = host.expire(left + Duration::from_secs(5));
+        assert!(sent.contains(&(9, ServerMessage::Forfeited { player: 1 })));
+        // Turn of forfeited player passes on.
+        match sent.last() {
+            Some((_, ServerMessage::State(state))) => {
+                assert_eq!(state.alive, vec![true, false, true]);
+                assert_eq!(state.turn, 2);
+            }
+            message => panic!("unexpected {:?}", message),
+        }
+        host.disconnect(3);
+        let sent = host.expire(Instant::now() + Duration::from_secs(5));
+        match sent.last() {
+            Some((_, ServerMessage::State(state))) => assert_eq!(state.winner, Some(0)),
+            message => panic!("unexpected {:?}", message),
+        }
+        assert!(matches!(
+            host.handle(1, join("alice"))[..],
+            [
+                (1, ServerMessage::Lobby { .. }),
+                (9, ServerMessage::Lobby { .. }),
+                (8, ServerMessage::Lobby { .. })
+            ]
+        ));
+    }
+
+    #[test]
+    fn timeouts() {
+        let time = TimeControl {
+            per_move: Some(Duration::from_secs(10)),
+            per_game: None,
+            on_timeout: TimeoutAction::RandomMove,
+        };
+        let mut host = GameHost::new(3, 3, 2, Rules::Classic).with_time(time);
+        host.handle(1, join("alice"));
+        let sent = host.handle(2, join("bob"));
+        match sent.last() {
+            Some((_, ServerMessage::State(state))) => {
+                assert_eq!(state.time_left, Some(vec![10_000, 10_000]))
+            }
+            message => panic!("unexpected {:?}", message),
+        }
+        assert_eq!(host.expire(Instant::now()), vec![]);
+        let sent = host.expire(Instant::now() + Duration::from_secs(10));
+        assert_eq!(
+            sent[0],
+            (
+                1,
+                ServerMessage::TimedOut {
+                    player: 0,
+                    action: TimeoutAction::RandomMove
+                }
+            )
+        );
+        assert!(matches!(
+            sent[2],
+            (1, ServerMessage::Moved { player: 0, .. })
+        ));
+        match sent.last() {
+            Some((_, ServerMessage::State(state))) => assert_eq!(state.turn, 1),
+            message => panic!("unexpected {:?}", message),
+        }
+    }
+
+    #[test]
+    fn long_lines() {
+        let mut lines = std::io::Cursor::new("{}\nlast");
+        assert_eq!(read_line(&mut lines).unwrap(), Some("{}\n".to_string()));
+        assert_eq!(read_line(&mut lines).unwrap(), Some("last".to_string()));
+        assert_eq!(read_line(&mut lines).unwrap(), None);
+        let long = "x".repeat(websocket::MAX_MESSAGE);
+        let mut lines = std::io::Cursor::new(format!("{}\n{}x", long, long));
+        assert_eq!(
+            read_line(&mut lines).unwrap().map(|line| line.len()),
+            Some(long.len() + 1)
+        );
+        assert!(read_line(&mut lines).is_err());
+    }
+
+    #[test]
+    fn rooms() {
+        let mut rooms = Rooms::new(3, 3, 2, Rules::Classic);
+        let create = |players| ClientMessage::Create {
+            name: "alice".to_string(),
+            rows: 4,
+            cols: 4,
+            players,
+            rules: Rules::Wrap,
+        };
+        assert!(matches!(
+            rooms.handle(1, create(9))[..],
+            [(1, ServerMessage::Error { .. })]
+        ));
+        let too_large = ClientMessage::Create {
+            name: "alice".to_string(),
+            rows: MAX_SIZE + 1,
+            cols: 4,
+            players: 2,
+            rules: Rules::Wrap,
+        };
+        assert_eq!(
+            rooms.handle(1, too_large),
+            vec![(
+                1,
+                ServerMessage::Error {
+                    message: "board needs 3 to 20 rows and columns".to_string()
+                }
+            )]
+        );
+        let sent = rooms.handle(1, create(2));
+        let (1, ServerMessage::Room { ref code }) = sent[0] else {
+            panic!("unexpected {:?}", sent);
+        };
+        let code = code.clone();
+        assert!(matches!(
+            rooms.handle(1, create(2))[..],
+            [(1, ServerMessage::Error { .. })]
+        ));
+        let join_room = |name: &str, room: &str| ClientMessage::Join {
+            name: name.to_string(),
+            room: Some(room.to_string()),
+        };
+        assert!(matches!(
+            rooms.handle(2, join_room("bob", "NONE"))[..],
+            [(2, ServerMessage::Error { .. })]
+        ));
+        // Default room is separate.
+        match rooms.handle(3, join("carol"))[..] {
+            [(3, ServerMessage::Lobby { ref names, .. })] => assert_eq!(names, &["carol"]),
+            ref sent => panic!("unexpected {:?}", sent),
+        }
+        let sent = rooms.handle(2, join_room("bob", &code));
+        let (_, token) = welcome(&sent, 2);
+        match sent.last() {
+            Some((_, ServerMessage::State(state))) => assert_eq!(state.rules, Rules::Wrap),
+            message => panic!("unexpected {:?}", message),
+        }
+        assert_eq!(
+            rooms.disconnect(1),
+            vec![(2, ServerMessage::Left { player: 0 })]
+        );
+        assert_eq!(rooms.disconnect(2), vec![]);
+        // Room is kept while players may return.
+        let sent = rooms.handle(4, ClientMessage::Rejoin { token });
+        assert_eq!(welcome(&sent, 4).0, 1);
+        rooms.disconnect(4);
+        rooms.expire(Instant::now() + GRACE);
+        // Room is gone once everyone left.
+        assert!(matches!(
+            rooms.handle(1, join_room("alice", &code))[..],
+            [(1, ServerMessage::Error { .. })]
+        ));
+    }
+
+    #[test]
+    fn game_over_opens_lobby() {
+        let mut host = GameHost::new(3, 3, 2, Rules::Classic);
+        host.handle(1, join("alice"));
+        host.handle(2, join("bob"));
+        for (client, row, col) in [(1, 0, 0), (2, 1, 0), (1, 0, 0)] {
+            host.handle(client, ClientMessage::Move { row, col });
+        }
+        match host.handle(1, join("alice"))[..] {
+            [(1, ServerMessage::Lobby { ref names, .. })] => assert_eq!(names.len(), 1),
+            ref sent => panic!("unexpected {:?}", sent),
+        }
+    }
+
+    #[test]
+    fn resign() {
+        let mut host =
+            GameHost::new(3, 3, 3, Rules::Classic).with_leave_policy(LeavePolicy::Neutral);
+        host.handle(1, join("alice"));
+        host.handle(2, join("bob"));
+        host.handle(3, join("carol"));
+        host.handle(1, ClientMessage::Move { row: 1, col: 1 });
+        let sent = host.handle(1, ClientMessage::Resign);
+        assert_eq!(sent[0], (1, ServerMessage::Resigned { player: 0 }));
+        match sent.last() {
+            Some((_, ServerMessage::State(state))) => {
+                assert_eq!(state.alive, vec![false, true, true]);
+                assert_eq!(state.cells[1][1], Some((0, 1)));
+                assert_eq!(state.turn, 1);
+            }
+            message => panic!("unexpected {:?}", message),
+        }
+        assert!(matches!(
+            host.handle(1, ClientMessage::Resign)[..],
+            [(1, ServerMessage::Error { .. })]
+        ));
+        let sent = host.handle(3, ClientMessage::Resign);
+        match sent.last() {
+            Some((_, ServerMessage::State(state))) => assert_eq!(state.winner, Some(1)),
+            message => panic!("unexpected {:?}", message),
+        }
+    }
+
+    #[test]
+    fn draw() {
+        let mut host = GameHost::new(3, 3, 2, Rules::Classic);
+        host.handle(1, join("alice"));
+        host.handle(2, join("bob"));
+        assert!(matches!(
+            host.handle(2, ClientMessage::AcceptDraw)[..],
+            [(2, ServerMessage::Error { .. })]
+        ));
+        let sent = host.handle(1, ClientMessage::OfferDraw);
+        assert_eq!(sent[0], (1, ServerMessage::DrawOffered { player: 0 }));
+        match sent.last() {
+            Some((_, ServerMessage::State(state))) => assert_eq!(state.draw_offers, vec![0]),
+            message => panic!("unexpected {:?}", message),
+        }
+        let sent = host.handle(2, ClientMessage::DeclineDraw);
+        assert_eq!(sent[0], (1, ServerMessage::DrawDeclined { player: 1 }));
+        host.handle(2, ClientMessage::OfferDraw);
+        let sent = host.handle(1, ClientMessage::AcceptDraw);
+        match sent.last() {
+            Some((_, ServerMessage::State(state))) => {
+                assert!(state.drawn && state.is_over());
+                assert_eq!(state.winner, None);
+                assert_eq!(state.standings.len(), 2);
+            }
+            message => panic!("unexpected {:?}", message),
+        }
+        // Game is over, so players are no longer in it.
+        assert!(matches!(
+            host.handle(1, ClientMessage::OfferDraw)[..],
+            [(1, ServerMessage::Error { .. })]
+        ));
+    }
+}
@@ -0,0 +1,324 @@
+//! Provides puzzles with predefined positions and goals.
+//!
+//! Puzzles are described in text, one after the other.
+//!
+//! ```text
+//! # Lines starting with `#` are ignored.
+//! puzzle First blood
+//! players 2
+//! turn 0
+//! goal win
+//! 01 .. ..
+//! 11 .. ..
+//! .. .. ..
+//! ```
+//!
+//! Each cell is either `..` for empty cell or owner followed by atoms.
+//! Goal can be `win`, `capture <cells>` or `eliminate <player>` of opponent.
+
+use crate::board::{Board, BoardState, CellState, MoveError, PositionError};
+use std::fmt::Display;
+
+/// Puzzle Errors.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PuzzleError {
+    /// Line could not be understood. The line number is stored.
+    InvalidLine(usize),
+    /// Cell could not be understood. The line number is stored.
+    InvalidCell(usize),
+    /// Puzzle is missing a field. The puzzle name and field are stored.
+    MissingField(String, &'static str),
+    /// Puzzle position is invalid. The puzzle name and error are stored.
+    InvalidPosition(String, PositionError),
+    /// Player to eliminate is not an opponent. The puzzle name is stored.
+    InvalidGoal(String),
+}
+
+/// Goal to be achieved by single move.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Goal {
+    /// Win the game this turn.
+    Win,
+    /// Capture at least given number of other players' cells.
+    Capture(usize),
+    /// Eliminate given player.
+    Eliminate(u8),
+}
+
+impl Display for Goal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Goal::Win => write!(f, "win this turn"),
+            Goal::Capture(cells) => write!(f, "capture {} cells in one move", cells),
+            Goal::Eliminate(player) => write!(f, "eliminate player {}", player),
+        }
+    }
+}
+
+/// Puzzle with position and goal.
+#[derive(Clone)]
+pub struct Puzzle {
+    name: String,
+    goal: Goal,
+    board: Board,
+}
+
+impl Puzzle {
+    /// Provides name of puzzle.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Provides goal of puzzle.
+    pub fn goal(&self) -> &Goal {
+        &self.goal
+    }
+
+    /// Provides starting position of puzzle.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Checks if move achieves goal of puzzle.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`MoveError`] if move is invalid.
+    ///
+    /// [`MoveError`]: crate::board::MoveError
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::puzzle::parse;
+    ///
+    /// let puzzles = parse(
+    ///     "puzzle First blood\nplayers 2\nturn 0\ngoal win\n01 .. ..\n11 .. ..\n.. .. ..\n",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(puzzles[0].check(0, 0), Ok(true));
+    /// assert_eq!(puzzles[0].check(2, 2), Ok(false));
+    /// ```
+    pub fn check(&self, row: usize, col: usize) -> Result<bool, MoveError> {
+        let player = self.board.current_player_id();
+        let mut board = self.board.clone();
+        board.player_move(player, row, col)?;
+        board.settle();
+        Ok(match self.goal {
+            Goal::Win => board.state() == &BoardState::GameOver(player),
+            Goal::Capture(cells) => {
+                let captured = self
+                    .board
+                    .cells()
+                    .into_iter()
+                    .flatten()
+                    .zip(board.cells().into_iter().flatten())
+                    .filter(|(before, after)| {
+                        matches!(before, CellState::NonEmpty(owner, _) if *owner != player)
+                            && matches!(after, CellState::NonEmpty(owner, _) if *owner == player)
+                    })
+                    .count();
+                captured >= cells
+            }
+            Goal::Eliminate(other) => !board
+                .cells()
+                .into_iter()
+                .flatten()
+                .any(|cell| matches!(cell, CellState::NonEmpty(owner, _) if owner == other)),
+        })
+    }
+
+    /// Provides all moves which achieve goal of puzzle.
+    pub fn solutions(&self) -> Vec<(usize, usize)> {
+        (0..self.board.rows())
+            .flat_map(|row| (0..self.board.cols()).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.check(row, col) == Ok(true))
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct PuzzleBuilder {
+    name: String,
+    players: Option<u8>,
+    turn: Option<u8>,
+    goal: Option<Goal>,
+    cells: Vec<Vec<CellState>>,
+}
+
+impl PuzzleBuilder {
+    fn build(self) -> Result<Puzzle, PuzzleError> {
+        let missing = |field| PuzzleError::MissingField(self.name.clone(), field);
+        let players = self.players.ok_or_else(|| missing("players"))?;
+        let turn = self.turn.unwrap_or(0);
+        let goal = self.goal.clone().ok_or_else(|| missing("goal"))?;
+        let board = Board::from_cells(self.cells, players, turn)
+            .map_err(|err| PuzzleError::InvalidPosition(self.name.clone(), err))?;
+        if let Goal::Eliminate(other) = goal {
+            if other >= players || other == board.current_player_id() {
+                return Err(PuzzleError::InvalidGoal(self.name));
+            }
+        }
+        Ok(Puzzle {
+            name: self.name,
+            goal,
+            board,
+        })
+    }
+}
+
+fn parse_goal(value: &str) -> Option<Goal> {
+    match value.split_once(' ') {
+        None if value == "win" => Some(Goal::Win),
+        Some(("capture", cells)) => cells.trim().parse().ok().map(Goal::Capture),
+        Some(("eliminate", player)) => player.trim().parse().ok().map(Goal::Eliminate),
+        _ => None,
+    }
+}
+
+fn parse_cell(cell: &str) -> Option<CellState> {
+    if cell == ".." {
+        return Some(CellState::Empty);
+    }
+    let mut digits = cell.chars().map(|c| c.to_digit(10));
+    match (digits.next(), digits.next(), digits.next()) {
+        (Some(Some(owner)), Some(Some(atoms)), None) => {
+            Some(CellState::NonEmpty(owner as u8, atoms as u8))
+        }
+        _ => None,
+    }
+}
+
+/// Parse puzzles from text.
+///
+/// # Errors
+///
+/// Will return [`PuzzleError`] if text is invalid.
+///
+/// [`PuzzleError`]: self::PuzzleError
+pub fn parse(text: &str) -> Result<Vec<Puzzle>, PuzzleError> {
+    let mut puzzles = vec![];
+    let mut current: Option<PuzzleBuilder> = None;
+    for (number, line) in text.lines().enumerate().map(|(i, l)| (i + 1, l.trim())) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        let value = value.trim();
+        if key == "puzzle" {
+            if let Some(builder) = current.take() {
+                puzzles.push(builder.build()?);
+            }
+            current = Some(PuzzleBuilder {
+                name: value.to_string(),
+                ..Default::default()
+            });
+            continue;
+        }
+        let builder = current.as_mut().ok_or(PuzzleError::InvalidLine(number))?;
+        let valid = match key {
+            "players" => {
+                builder.players = value.parse().ok();
+                builder.players.is_some()
+            }
+            "turn" => {
+                builder.turn = value.parse().ok();
+                builder.turn.is_some()
+            }
+            "goal" => {
+                builder.goal = parse_goal(value);
+                builder.goal.is_some()
+            }
+            _ => {
+                let row = line
+                    .split_whitespace()
+                    .map(parse_cell)
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or(PuzzleError::InvalidCell(number))?;
+                builder.cells.push(row);
+                true
+            }
+        };
+        if !valid {
+            return Err(PuzzleError::InvalidLine(number));
+        }
+    }
+    if let Some(builder) = current {
+        puzzles.push(builder.build()?);
+    }
+    Ok(puzzles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLES: &str = "
+# two puzzles
+puzzle Capture
+players 3
+turn 2
+goal capture 2
+21 01 ..
+11 .. ..
+.. .. 21
+
+puzzle Eliminate
+players 3
+goal eliminate 2
+01 21 ..
+.. .. ..
+.. .. 11
+";
+
+    #[test]
+    fn parse_multiple_puzzles() {
+        let puzzles = parse(PUZZLES).unwrap();
+        assert_eq!(puzzles.len(), 2);
+        assert_eq!(puzzles[0].name(), "Capture");
+        assert_eq!(puzzles[0].goal(), &Goal::Capture(2));
+        assert_eq!(puzzles[0].board().current_player_id(), 2);
+        assert_eq!(puzzles[1].board().current_player_id(), 0);
+    }
+
+    #[test]
+    fn solutions_of_puzzles() {
+        let puzzles = parse(PUZZLES).unwrap();
+        assert_eq!(puzzles[0].solutions(), vec![(0, 0)]);
+        assert_eq!(puzzles[1].solutions(), vec![(0, 0)]);
+    }
+
+    #[test]
+    fn invalid_puzzles() {
+        assert_eq!(parse("players 2").err(), Some(PuzzleError::InvalidLine(1)));
+        assert_eq!(
+            parse("puzzle a\nplayers 2\ngoal lose").err(),
+            Some(PuzzleError::InvalidLine(3))
+        );
+        assert_eq!(
+            parse("puzzle a\nplayers 2\n.. x").err(),
+            Some(PuzzleError::InvalidCell(3))
+        );
+        assert_eq!(
+            parse("puzzle a\nplayers 2\n.. .. ..\n.. .. ..\n.. .. ..").err(),
+            Some(PuzzleError::MissingField("a".to_string(), "goal"))
+        );
+        assert_eq!(
+            parse("puzzle a\nplayers 2\ngoal win\n.. .. ..\n.. 14 ..\n.. .. ..").err(),
+            Some(PuzzleError::InvalidPosition(
+                "a".to_string(),
+                PositionError::InvalidAtoms(1, 1)
+            ))
+        );
+        for goal in ["eliminate 3", "eliminate 1"] {
+            let text = format!(
+                "puzzle a\nplayers 3\nturn 1\ngoal {}\n01 11 21\n.. .. ..\n.. .. ..",
+                goal
+            );
+            assert_eq!(
+                parse(&text).err(),
+                Some(PuzzleError::InvalidGoal("a".to_string()))
+            );
+        }
+    }
+}
@@ -0,0 +1,943 @@
+//! Provides record of game which can be saved as text and replayed.
+//!
+//! Records are plain text, one field or move per line.
+//!
+//! ```text
+//! # Lines starting with `#` are ignored.
+//! rows 10
+//! cols 10
+//! players 2
+//! rules classic
+//! move 0 4 4
+//! move 1 0 0
+//! ```
+//!
+//! Each move is the player followed by row and column. Players giving up
+//! are listed between moves, e.g. `resign 1` for player 1. Games players
+//! agreed to draw end with `draw`.
+//!
+//! Team games add number of teams, followed by `capture` if teammates
+//! capture each other, e.g. `teams 2 capture`.
+//!
+//! Saved games in progress also list whose turn it is and which players
+//! are eliminated, one `0` or `1` for each player. These lines are checked
+//! against the moves when loading.
+//!
+//! ```text
+//! rows 10
+//! cols 10
+//! players 3
+//! rules classic
+//! turn 2
+//! eliminated 0 1 0
+//! move 0 0 0
+//! ```
+//!
+//! Records can also be written in compact notation, short enough for links.
+//! Board comes first as `<rows>x<cols>-<players>-<rules>`, followed by
+//! `-teams<count>` and `-capture` for team games. Moves follow after `.`,
+//! each as column letters and row number counted from 1, like `a1` for the
+//! top left cell. Players of moves are left out as they follow from turns.
+//! Players resigning are written as `-` followed by player, and drawn games
+//! end with `=`.
+//!
+//! ```text
+//! 10x10-3-classic.e5a1-2e6=
+//! ```
+
+use crate::board::{Board, BoardState, MoveError, Rules, Teams, MAX_PLAYERS, MAX_SIZE, MIN_SIZE};
+use std::fmt::Display;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Record Errors.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RecordError {
+    /// Line could not be understood. The line number is stored.
+    InvalidLine(usize),
+    /// Record is missing a field. The field is stored.
+    MissingField(&'static str),
+    /// Board has less than 3 rows or columns.
+    BoardTooSmall,
+    /// Board has less than 2 players.
+    TooFewPlayers,
+    /// Players cannot be split evenly into teams.
+    UnevenTeams,
+    /// Move could not be played. The move number and error are stored.
+    InvalidMove(usize, MoveError),
+    /// Player could not resign. Moves played before and error are stored.
+    InvalidResign(usize, MoveError),
+    /// Players could not agree to a draw. The error is stored.
+    InvalidDraw(MoveError),
+    /// Saved field does not match the moves. The field is stored.
+    Mismatch(&'static str),
+    /// Compact notation could not be understood.
+    InvalidNotation,
+}
+
+/// Turn of game, players of moves follow from order of turns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn {
+    /// Move to row and column.
+    Move(usize, usize),
+    /// Player gave up.
+    Resign(u8),
+}
+
+/// Record of game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord {
+    /// Rows in board.
+    pub rows: usize,
+    /// Columns in board.
+    pub cols: usize,
+    /// Number of players.
+    pub players: u8,
+    /// Rules of board.
+    pub rules: Rules,
+    /// Teams of players, `None` when everyone plays for themselves.
+    pub teams: Option<Teams>,
+    /// Moves as player, row and column.
+    pub moves: Vec<(u8, usize, usize)>,
+    /// Players who resigned, with moves played before.
+    pub resigned: Vec<(usize, u8)>,
+    /// Whether players agreed to a draw after the last move.
+    pub drawn: bool,
+}
+
+impl GameRecord {
+    /// Create record of game without moves.
+    pub fn new(rows: usize, cols: usize, players: u8, rules: Rules) -> Self {
+        Self {
+            rows,
+            cols,
+            players,
+            rules,
+            teams: None,
+            moves: vec![],
+            resigned: vec![],
+            drawn: false,
+        }
+    }
+
+    /// Provides players who resigned once `moves` moves were played.
+    pub fn resigned_after(&self, moves: usize) -> impl Iterator<Item = u8> + '_ {
+        self.resigned
+            .iter()
+            .filter(move |(before, _)| *before == moves)
+            .map(|(_, player)| *player)
+    }
+
+    /// Provides moves and resignations in order they were made.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::Rules;
+    /// use chain_reaction::record::{GameRecord, Turn};
+    ///
+    /// let mut record = GameRecord::new(3, 3, 3, Rules::Classic);
+    /// record.moves = vec![(0, 0, 0), (1, 1, 1)];
+    /// record.resigned = vec![(1, 2)];
+    /// assert_eq!(
+    ///     record.turns(),
+    ///     vec![Turn::Move(0, 0), Turn::Resign(2), Turn::Move(1, 1)]
+    /// );
+    /// ```
+    pub fn turns(&self) -> Vec<Turn> {
+        let mut turns = vec![];
+        for moves in 0..=self.moves.len() {
+            turns.extend(self.resigned_after(moves).map(Turn::Resign));
+            if let Some(&(_, row, col)) = self.moves.get(moves) {
+                turns.push(Turn::Move(row, col));
+            }
+        }
+        turns
+    }
+
+    /// Keeps first `moves` moves, along with resignations made by then.
+    /// Draw is kept only along with all moves.
+    pub fn truncate(&mut self, moves: usize) {
+        self.drawn &= moves >= self.moves.len();
+        self.moves.truncate(moves);
+        self.resigned.retain(|(before, _)| *before <= moves);
+    }
+
+    /// Resigns players who gave up once `moves` moves were played.
+    fn resign(&self, board: &mut Board, moves: usize) -> Result<(), RecordError> {
+        for player in self.resigned_after(moves) {
+            board
+                .resign(player)
+                .map_err(|err| RecordError::InvalidResign(moves, err))?;
+        }
+        Ok(())
+    }
+
+    /// Ends game in draw, agreed by everyone in the game.
+    fn draw(&self, board: &mut Board) -> Result<(), RecordError> {
+        for player in 0..board.player_count() {
+            if board.is_player_alive(player) && !board.is_over() {
+                board.offer_draw(player).map_err(RecordError::InvalidDraw)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Provides new board for record, without moves.
+    pub(crate) fn board(&self) -> Result<Board, RecordError> {
+        if self.rows < 3 || self.cols < 3 {
+            return Err(RecordError::BoardTooSmall);
+        }
+        if self.players < 2 {
+            return Err(RecordError::TooFewPlayers);
+        }
+        let board = Board::with_rules(self.rows, self.cols, self.players, self.rules);
+        match self.teams {
+            Some(teams) if teams.count < 2 || !self.players.is_multiple_of(teams.count) => {
+                Err(RecordError::UnevenTeams)
+            }
+            Some(teams) => Ok(board.with_teams(teams)),
+            None => Ok(board),
+        }
+    }
+
+    /// Play all moves of record on new board.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`RecordError`] if board is invalid or move cannot be played.
+    ///
+    /// [`RecordError`]: self::RecordError
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::{BoardState, Rules};
+    /// use chain_reaction::record::GameRecord;
+    ///
+    /// let mut record = GameRecord::new(3, 3, 2, Rules::Classic);
+    /// record.moves = vec![(0, 0, 0), (1, 1, 0), (0, 0, 0)];
+    /// assert_eq!(record.replay().unwrap().state(), &BoardState::GameOver(0));
+    /// ```
+    pub fn replay(&self) -> Result<Board, RecordError> {
+        self.replay_moves(self.moves.len())
+    }
+
+    /// Play first `count` moves of record on new board, along with
+    /// resignations made by then and draw after all moves.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`RecordError`] if board is invalid or move cannot be played.
+    ///
+    /// [`RecordError`]: self::RecordError
+    pub fn replay_moves(&self, count: usize) -> Result<Board, RecordError> {
+        let mut board = self.board()?;
+        for (number, &(player, row, col)) in self.moves.iter().take(count).enumerate() {
+            self.resign(&mut board, number)?;
+            board
+                .player_move(player, row, col)
+                .map_err(|err| RecordError::InvalidMove(number + 1, err))?;
+            board.settle();
+        }
+        self.resign(&mut board, count.min(self.moves.len()))?;
+        if self.drawn && count >= self.moves.len() {
+            self.draw(&mut board)?;
+        }
+        Ok(board)
+    }
+
+    /// Play first `count` moves of record, stopping explosions of last move
+    /// after `waves` waves.
+    ///
+    /// Board is settled if explosions end before `waves` waves.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`RecordError`] if board is invalid or move cannot be played.
+    ///
+    /// [`RecordError`]: self::RecordError
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::{BoardState, Rules};
+    /// use chain_reaction::record::GameRecord;
+    ///
+    /// let mut record = GameRecord::new(3, 3, 2, Rules::Classic);
+    /// record.moves = vec![(0, 0, 0), (1, 2, 2), (0, 0, 0)];
+    /// let board = record.replay_waves(3, 0).unwrap();
+    /// assert_eq!(board.state(), &BoardState::Explosion(vec![(0, 0)]));
+    /// let board = record.replay_waves(3, 5).unwrap();
+    /// assert_eq!(board.state(), &BoardState::Wait);
+    /// ```
+    pub fn replay_waves(&self, count: usize, waves: usize) -> Result<Board, RecordError> {
+        let mut board = self.replay_moves(count.saturating_sub(1))?;
+        let last = count.checked_sub(1).and_then(|index| self.moves.get(index));
+        if let Some(&(player, row, col)) = last {
+            board
+                .player_move(player, row, col)
+                .map_err(|err| RecordError::InvalidMove(count, err))?;
+            for _ in 0..waves {
+                if !matches!(board.state(), BoardState::Explosion(_)) {
+                    break;
+                }
+                board.next_iteration();
+            }
+            if !matches!(board.state(), BoardState::Explosion(_)) {
+                board.settle();
+                self.resign(&mut board, count)?;
+                if self.drawn && count >= self.moves.len() {
+                    self.draw(&mut board)?;
+                }
+            }
+        }
+        Ok(board)
+    }
+
+    /// Parse record from text.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`RecordError`] if text is invalid.
+    ///
+    /// [`RecordError`]: self::RecordError
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::Rules;
+    /// use chain_reaction::record::GameRecord;
+    ///
+    /// let mut record = GameRecord::new(5, 4, 3, Rules::Wrap);
+    /// record.moves.push((0, 1, 2));
+    /// assert_eq!(GameRecord::parse(&record.to_string()), Ok(record));
+    ///
+    /// let text = "rows 3\ncols 3\nplayers 4\nteams 2 capture";
+    /// assert_eq!(GameRecord::parse(text).unwrap().teams.map(|t| t.capture), Some(true));
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, RecordError> {
+        Fields::parse(text).map(|fields| fields.record)
+    }
+
+    /// Writes record in compact notation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::{Rules, Teams};
+    /// use chain_reaction::record::GameRecord;
+    ///
+    /// let mut record = GameRecord::new(5, 4, 4, Rules::Wrap);
+    /// record.teams = Some(Teams::new(2));
+    /// record.moves = vec![(0, 1, 2), (1, 0, 0), (2, 4, 3)];
+    /// assert_eq!(record.to_notation(), "5x4-4-wrap-teams2.c2a1d5");
+    /// ```
+    pub fn to_notation(&self) -> String {
+        let mut notation = format!(
+            "{}x{}-{}-{}",
+            self.rows,
+            self.cols,
+            self.players,
+            self.rules.name()
+        );
+        if let Some(teams) = self.teams {
+            notation += &format!("-teams{}", teams.count);
+            if teams.capture {
+                notation += "-capture";
+            }
+        }
+        let turns = self.turns();
+        if !turns.is_empty() || self.drawn {
+            notation.push('.');
+        }
+        for turn in turns {
+            match turn {
+                Turn::Move(row, col) => notation += &format!("{}{}", column_letters(col), row + 1),
+                Turn::Resign(player) => notation += &format!("-{}", player),
+            }
+        }
+        if self.drawn {
+            notation.push('=');
+        }
+        notation
+    }
+
+    /// Reads record from compact notation, playing its moves to know who
+    /// made them.
+    ///
+    /// Boards are limited to [`MIN_SIZE`] to [`MAX_SIZE`] rows and columns
+    /// and [`MAX_PLAYERS`] players, as notation may come from anywhere.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`RecordError`] if notation is invalid or move cannot be played.
+    ///
+    /// [`RecordError`]: self::RecordError
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::Rules;
+    /// use chain_reaction::record::{GameRecord, RecordError};
+    ///
+    /// let record = GameRecord::from_notation("3x3-2-classic.a1b2").unwrap();
+    /// assert_eq!(record.rules, Rules::Classic);
+    /// assert_eq!(record.moves, vec![(0, 0, 0), (1, 1, 1)]);
+    /// assert_eq!(GameRecord::from_notation("3x3-2-hex"), Err(RecordError::InvalidNotation));
+    /// ```
+    pub fn from_notation(notation: &str) -> Result<Self, RecordError> {
+        let (header, moves) = notation.split_once('.').unwrap_or((notation, ""));
+        let (moves, drawn) = match moves.strip_suffix('=') {
+            Some(moves) => (moves, true),
+            None => (moves, false),
+        };
+        let mut parts = header.split('-');
+        let (rows, cols) = parts
+            .next()
+            .and_then(|size| size.split_once('x'))
+            .ok_or(RecordError::InvalidNotation)?;
+        let mut record = GameRecord::new(
+            rows.parse().map_err(|_| RecordError::InvalidNotation)?,
+            cols.parse().map_err(|_| RecordError::InvalidNotation)?,
+            parts
+                .next()
+                .and_then(|players| players.parse().ok())
+                .ok_or(RecordError::InvalidNotation)?,
+            parts
+                .next()
+                .and_then(Rules::from_name)
+                .ok_or(RecordError::InvalidNotation)?,
+        );
+        if let Some(teams) = parts.next() {
+            let count = teams
+                .strip_prefix("teams")
+                .and_then(|count| count.parse().ok())
+                .ok_or(RecordError::InvalidNotation)?;
+            let capture = match parts.next() {
+                Some("capture") => true,
+                Some(_) => return Err(RecordError::InvalidNotation),
+                None => false,
+            };
+            record.teams = Some(Teams { count, capture });
+        }
+        // Notation comes from links, boards are bounded as in menu before
+        // being built.
+        let sizes = MIN_SIZE..=MAX_SIZE;
+        if parts.next().is_some()
+            || !sizes.contains(&record.rows)
+            || !sizes.contains(&record.cols)
+            || record.players > MAX_PLAYERS
+        {
+            return Err(RecordError::InvalidNotation);
+        }
+        let turns = parse_turns(moves).ok_or(RecordError::InvalidNotation)?;
+        let mut board = record.board()?;
+        for turn in turns {
+            let moves = record.moves.len();
+            match turn {
+                Turn::Move(row, col) => {
+                    let player = board.current_player_id();
+                    board
+                        .player_move(player, row, col)
+                        .map_err(|err| RecordError::InvalidMove(moves + 1, err))?;
+                    board.settle();
+                    record.moves.push((player, row, col));
+                }
+                Turn::Resign(player) => {
+                    board
+                        .resign(player)
+                        .map_err(|err| RecordError::InvalidResign(moves, err))?;
+                    record.resigned.push((moves, player));
+                }
+            }
+        }
+        if drawn {
+            record.draw(&mut board)?;
+            record.drawn = true;
+        }
+        Ok(record)
+    }
+
+    fn write_header(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# chain reaction game")?;
+        writeln!(f, "rows {}", self.rows)?;
+        writeln!(f, "cols {}", self.cols)?;
+        writeln!(f, "players {}", self.players)?;
+        writeln!(f, "rules {}", self.rules.name())?;
+        match self.teams {
+            Some(teams) if teams.capture => writeln!(f, "teams {} capture", teams.count),
+            Some(teams) => writeln!(f, "teams {}", teams.count),
+            None => Ok(()),
+        }
+    }
+
+    fn write_moves(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for moves in 0..=self.moves.len() {
+            for player in self.resigned_after(moves) {
+                writeln!(f, "resign {}", player)?;
+            }
+            if let Some((player, row, col)) = self.moves.get(moves) {
+                writeln!(f, "move {} {} {}", player, row, col)?;
+            }
+        }
+        if self.drawn {
+            writeln!(f, "draw")?;
+        }
+        Ok(())
+    }
+}
+
+/// Record along with saved turn and eliminated players.
+struct Fields {
+    record: GameRecord,
+    turn: Option<u8>,
+    eliminated: Option<Vec<bool>>,
+}
+
+impl Fields {
+    fn parse(text: &str) -> Result<Self, RecordError> {
+        let mut rows = None;
+        let mut cols = None;
+        let mut players = None;
+        let mut rules = Rules::Classic;
+        let mut teams = None;
+        let mut moves = vec![];
+        let mut resigned = vec![];
+        let mut drawn = false;
+        let mut turn = None;
+        let mut eliminated = None;
+        for (number, line) in text.lines().enumerate().map(|(i, l)| (i + 1, l.trim())) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            let value = value.trim();
+            let valid = match key {
+                "rows" => {
+                    rows = value.parse().ok();
+                    rows.is_some()
+                }
+                "cols" => {
+                    cols = value.parse().ok();
+                    cols.is_some()
+                }
+                "players" => {
+                    players = value.parse().ok();
+                    players.is_some()
+                }
+                "rules" => Rules::from_name(value).map(|r| rules = r).is_some(),
+                "teams" => {
+                    teams = parse_teams(value);
+                    teams.is_some()
+                }
+                "move" => parse_move(value).map(|m| moves.push(m)).is_some(),
+                "resign" => value
+                    .parse()
+                    .ok()
+                    .map(|player| resigned.push((moves.len(), player)))
+                    .is_some(),
+                "draw" => {
+                    drawn = true;
+                    value.is_empty()
+                }
+                "turn" => {
+                    turn = value.parse().ok();
+                    turn.is_some()
+                }
+                "eliminated" => {
+                    eliminated = parse_mask(value);
+                    eliminated.is_some()
+                }
+                _ => false,
+            };
+            if !valid {
+                return Err(RecordError::InvalidLine(number));
+            }
+        }
+        let record = GameRecord {
+            rows: rows.ok_or(RecordError::MissingField("rows"))?,
+            cols: cols.ok_or(RecordError::MissingField("cols"))?,
+            players: players.ok_or(RecordError::MissingField("players"))?,
+            rules,
+            teams,
+            moves,
+            resigned,
+            drawn,
+        };
+        Ok(Self {
+            record,
+            turn,
+            eliminated,
+        })
+    }
+}
+
+fn parse_mask(value: &str) -> Option<Vec<bool>> {
+    value
+        .split_whitespace()
+        .map(|bit| match bit {
+            "0" => Some(false),
+            "1" => Some(true),
+            _ => None,
+        })
+        .collect()
+}
+
+fn parse_teams(value: &str) -> Option<Teams> {
+    let (count, capture) = match value.split_once(' ') {
+        Some((count, "capture")) => (count, true),
+        Some(_) => return None,
+        None => (value, false),
+    };
+    Some(Teams {
+        count: count.parse().ok()?,
+        capture,
+    })
+}
+
+/// Names column with letters, `a` to `z` and then `aa`, `ab` and so on.
+///
+/// # Examples
+///
+/// ```
+/// use chain_reaction::record::column_letters;
+///
+/// assert_eq!(column_letters(2), "c");
+/// assert_eq!(column_letters(27), "ab");
+/// ```
+pub fn column_letters(col: usize) -> String {
+    let mut letters = vec![];
+    let mut rest = col + 1;
+    while rest > 0 {
+        rest -= 1;
+        letters.push((b'a' + (rest % 26) as u8) as char);
+        rest /= 26;
+    }
+    letters.iter().rev().collect()
+}
+
+/// Reads cell named by column letters and row from 1, like `c4`, as row
+/// and column.
+///
+/// # Examples
+///
+/// ```
+/// use chain_reaction::record::parse_cell;
+///
+/// assert_eq!(parse_cell("c4"), Some((3, 2)));
+/// assert_eq!(parse_cell("ab10"), Some((9, 27)));
+/// assert_eq!(parse_cell("c0"), None);
+/// assert_eq!(parse_cell("c4d"), None);
+/// ```
+pub fn parse_cell(name: &str) -> Option<(usize, usize)> {
+    let mut chars = name.chars().peekable();
+    let cell = read_cell(&mut chars)?;
+    match chars.next() {
+        None => Some(cell),
+        Some(_) => None,
+    }
+}
+
+/// Reads next cell named by column letters and row.
+fn read_cell(chars: &mut Peekable<Chars>) -> Option<(usize, usize)> {
+    let mut col = 0usize;
+    while let Some(letter) = chars.next_if(char::is_ascii_lowercase) {
+        let value = letter as usize - 'a' as usize + 1;
+        col = col.checked_mul(26)?.checked_add(value)?;
+    }
+    let mut row = 0usize;
+    while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+        let value = digit as usize - '0' as usize;
+        row = row.checked_mul(10)?.checked_add(value)?;
+    }
+    if col == 0 || row == 0 {
+        return None;
+    }
+    Some((row - 1, col - 1))
+}
+
+/// Reads turns in compact notation.
+fn parse_turns(turns: &str) -> Option<Vec<Turn>> {
+    let mut parsed = vec![];
+    let mut chars = turns.chars().peekable();
+    while chars.peek().is_some() {
+        if chars.next_if_eq(&'-').is_some() {
+            let mut player = String::new();
+            while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                player.push(digit);
+            }
+            parsed.push(Turn::Resign(player.parse().ok()?));
+        } else {
+            let (row, col) = read_cell(&mut chars)?;
+            parsed.push(Turn::Move(row, col));
+        }
+    }
+    Some(parsed)
+}
+
+fn parse_move(value: &str) -> Option<(u8, usize, usize)> {
+    let mut parts = value.split_whitespace();
+    let player = parts.next()?.parse().ok()?;
+    let row = parts.next()?.parse().ok()?;
+    let col = parts.next()?.parse().ok()?;
+    match parts.next() {
+        None => Some((player, row, col)),
+        Some(_) => None,
+    }
+}
+
+impl Display for GameRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_header(f)?;
+        self.write_moves(f)
+    }
+}
+
+/// Game in progress which can be saved and resumed later.
+///
+/// Besides the record, saved text lists whose turn it is and eliminated players.
+#[derive(Clone)]
+pub struct SavedGame {
+    /// Record of moves played so far.
+    pub record: GameRecord,
+    /// Board after all moves of record.
+    pub board: Board,
+}
+
+impl SavedGame {
+    /// Create saved game by replaying record.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`RecordError`] if record cannot be replayed.
+    ///
+    /// [`RecordError`]: self::RecordError
+    pub fn new(record: GameRecord) -> Result<Self, RecordError> {
+        let board = record.replay()?;
+        Ok(Self { record, board })
+    }
+
+    /// Parse saved game, plain records without turn are accepted too.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`RecordError`] if text is invalid or saved turn and
+    /// eliminated players do not match the moves.
+    ///
+    /// [`RecordError`]: self::RecordError
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chain_reaction::board::Rules;
+    /// use chain_reaction::record::{GameRecord, RecordError, SavedGame};
+    ///
+    /// let mut record = GameRecord::new(3, 3, 2, Rules::Classic);
+    /// record.moves.push((0, 1, 1));
+    /// let saved = SavedGame::new(record).unwrap();
+    /// let text = saved.to_string();
+    /// assert!(text.contains("turn 1\n"));
+    /// assert_eq!(SavedGame::parse(&text).unwrap().board.current_player_id(), 1);
+    /// assert_eq!(
+    ///     SavedGame::parse(&text.replace("turn 1", "turn 0")).err(),
+    ///     Some(RecordError::Mismatch("turn"))
+    /// );
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, RecordError> {
+        let fields = Fields::parse(text)?;
+        let saved = Self::new(fields.record)?;
+        if matches!(fields.turn, Some(turn) if turn != saved.board.current_player_id()) {
+            return Err(RecordError::Mismatch("turn"));
+        }
+        if matches!(fields.eliminated, Some(ref eliminated) if *eliminated != saved.eliminated()) {
+            return Err(RecordError::Mismatch("eliminated"));
+        }
+        Ok(saved)
+    }
+
+    /// Provides for each player whether player is eliminated.
+    pub fn eliminated(&self) -> Vec<bool> {
+        (0..self.record.players)
+            .map(|player| !self.board.is_player_alive(player))
+            .collect()
+    }
+}
+
+impl Display for SavedGame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let record = &self.record;
+        record.write_header(f)?;
+        if !self.board.is_over() {
+            writeln!(f, "turn {}", self.board.current_player_id())?;
+        }
+        let eliminated: Vec<&str> = self
+            .eliminated()
+            .iter()
+            .map(|out| if *out { "1" } else { "0" })
+            .collect();
+        writeln!(f, "eliminated {}", eliminated.join(" "))?;
+        record.write_moves(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_records() {
+        assert_eq!(
+            GameRecord::parse("rows 3\ncols 3\nplayers 2\nmove 0 1"),
+            Err(RecordError::InvalidLine(4))
+        );
+        assert_eq!(
+            GameRecord::parse("rows 3\nplayers 2"),
+            Err(RecordError::MissingField("cols"))
+        );
+        assert_eq!(
+            GameRecord::parse("rows 3\ncols 3\nplayers 2\nrules hex"),
+            Err(RecordError::InvalidLine(4))
+        );
+        let record = GameRecord::parse("rows 3\ncols 3\nplayers 3\nteams 2").unwrap();
+        assert_eq!(record.replay().err(), Some(RecordError::UnevenTeams));
+    }
+
+    #[test]
+    fn saved_game() {
+        let mut record = GameRecord::new(3, 3, 3, Rules::Classic);
+        record.moves = vec![(0, 0, 0), (1, 2, 2), (2, 0, 2), (0, 0, 0)];
+        let saved = SavedGame::new(record.clone()).unwrap();
+        assert_eq!(saved.eliminated(), vec![false, false, false]);
+        let text = saved.to_string();
+        assert!(text.contains("turn 1\neliminated 0 0 0\n"), "{}", text);
+        assert_eq!(GameRecord::parse(&text), Ok(record.clone()));
+        assert_eq!(
+            SavedGame::parse(&text.replace("eliminated 0 0 0", "eliminated 0 1 0")).err(),
+            Some(RecordError::Mismatch("eliminated"))
+        );
+        assert_eq!(
+            SavedGame::parse(&text.replace("eliminated 0 0 0", "eliminated 0 2 0")).err(),
+            Some(RecordError::InvalidLine(7))
+        );
+        assert_eq!(
+            SavedGame::parse(&record.to_string()).unwrap().record,
+            record
+        );
+    }
+
+    #[test]
+    fn compact_notation() {
+        let mut record = GameRecord::new(3, 20, 3, Rules::Classic);
+        record.moves = vec![(0, 2, 19), (1, 0, 18), (2, 1, 17)];
+        let notation = record.to_notation();
+        assert_eq!(notation, "3x20-3-classic.t3s1r2");
+        assert_eq!(GameRecord::from_notation(&notation), Ok(record));
+        assert_eq!(
+            GameRecord::from_notation("3x3-2-classic"),
+            Ok(GameRecord::new(3, 3, 2, Rules::Classic))
+        );
+        for invalid in [
+            "3x3-classic",
+            "3x3-2-classic.a",
+            "3x3-2-classic.1a",
+            "3x3-2-classic-teams",
+            "3x3-2-classic.A1",
+            "100000x100000-255-classic",
+            "21x3-2-classic",
+            "2x3-2-classic",
+            "3x3-9-classic",
+        ] {
+            assert_eq!(
+                GameRecord::from_notation(invalid),
+                Err(RecordError::InvalidNotation),
+                "{}",
+                invalid
+            );
+        }
+        assert_eq!(
+            GameRecord::from_notation("3x3-2-classic.d1").err(),
+            Some(RecordError::InvalidMove(1, MoveError::MoveOutsideBoard))
+        );
+        assert_eq!(
+            GameRecord::from_notation("3x3-3-classic-teams2").err(),
+            Some(RecordError::UnevenTeams)
+        );
+    }
+
+    #[test]
+    fn resignations() {
+        let mut record = GameRecord::new(3, 3, 3, Rules::Classic);
+        record.moves = vec![(0, 0, 0), (1, 2, 2), (0, 0, 2)];
+        record.resigned = vec![(2, 2)];
+        let board = record.replay().unwrap();
+        assert!(!board.is_player_alive(2));
+        assert_eq!(board.current_player_id(), 1);
+        assert!(!record.replay_moves(2).unwrap().is_player_alive(2));
+        let saved = SavedGame::new(record.clone()).unwrap();
+        let text = saved.to_string();
+        assert!(
+            text.contains("move 1 2 2\nresign 2\nmove 0 0 2\n"),
+            "{}",
+            text
+        );
+        assert_eq!(saved.eliminated(), vec![false, false, true]);
+        assert_eq!(SavedGame::parse(&text).unwrap().record, record);
+        let notation = record.to_notation();
+        assert_eq!(notation, "3x3-3-classic.a1c3-2c1");
+        assert_eq!(GameRecord::from_notation(&notation), Ok(record.clone()));
+        let mut taken_back = record.clone();
+        taken_back.truncate(1);
+        assert_eq!(taken_back.resigned, vec![]);
+        record.resigned.push((3, 2));
+        assert_eq!(
+            record.replay().err(),
+            Some(RecordError::InvalidResign(3, MoveError::PlayerOut))
+        );
+    }
+
+    #[test]
+    fn draws() {
+        let mut record = GameRecord::new(3, 3, 2, Rules::Classic);
+        record.moves = vec![(0, 0, 0), (1, 2, 2)];
+        record.drawn = true;
+        assert_eq!(record.replay().unwrap().state(), &BoardState::Draw);
+        assert_eq!(record.replay_moves(1).unwrap().state(), &BoardState::Wait);
+        assert_eq!(
+            record.replay_waves(2, 0).unwrap().state(),
+            &BoardState::Draw
+        );
+        let saved = SavedGame::new(record.clone()).unwrap();
+        let text = saved.to_string();
+        assert!(text.ends_with("move 1 2 2\ndraw\n"), "{}", text);
+        assert!(!text.contains("turn"), "{}", text);
+        assert_eq!(SavedGame::parse(&text).unwrap().record, record);
+        assert_eq!(record.to_notation(), "3x3-2-classic.a1c3=");
+        assert_eq!(
+            GameRecord::from_notation("3x3-2-classic.a1c3="),
+            Ok(record.clone())
+        );
+        let mut unplayed = GameRecord::new(3, 3, 2, Rules::Classic);
+        unplayed.drawn = true;
+        assert_eq!(unplayed.to_notation(), "3x3-2-classic.=");
+        assert_eq!(GameRecord::from_notation("3x3-2-classic.="), Ok(unplayed));
+        record.truncate(1);
+        assert!(!record.drawn);
+        assert_eq!(
+            GameRecord::parse("rows 3\ncols 3\nplayers 2\ndraw 1"),
+            Err(RecordError::InvalidLine(4))
+        );
+    }
+
+    #[test]
+    fn invalid_replays() {
+        let mut record = GameRecord::new(2, 3, 2, Rules::Classic);
+        assert_eq!(record.replay().err(), Some(RecordError::BoardTooSmall));
+        record.rows = 3;
+        record.moves = vec![(0, 0, 0), (0, 1, 1)];
+        assert_eq!(
+            record.replay().err(),
+            Some(RecordError::InvalidMove(2, MoveError::NotCurrentPlayerMove))
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! Provides statistics of game, collected from board as game is played.
+//!
+//! Stats are told about each move and each step of its explosions, the
+//! same points where boards are shown to players.
+//!
+//! ```
+//! use chain_reaction::board::Board;
+//! use chain_reaction::stats::GameStats;
+//!
+//! let mut board = Board::new(3, 3, 2);
+//! let mut stats = GameStats::new(2);
+//! for (player, row, col) in [(0, 0, 0), (1, 1, 0), (0, 0, 0)] {
+//!     board.player_move(player, row, col).unwrap();
+//!     stats.moved(player, &board);
+//!     while board.next_iteration() {
+//!         stats.iterated(&board);
+//!     }
+//!     stats.iterated(&board);
+//! }
+//! assert_eq!(stats.players()[0].atoms_placed, 2);
+//! assert_eq!(stats.players()[0].cells_captured, 1);
+//! assert_eq!(stats.timeline().last(), Some(&vec![2, 0]));
+//! ```
+
+use crate::board::{Board, BoardState, CellState};
+use crate::record::{GameRecord, RecordError};
+use serde::{Deserialize, Serialize};
+
+/// Explosions caused by one move.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Chain {
+    /// Explosion waves.
+    pub waves: usize,
+    /// Cells exploded over all waves.
+    pub cells: usize,
+}
+
+/// Statistics of one player.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerStats {
+    /// Moves made, each placing one atom.
+    pub atoms_placed: usize,
+    /// Cells exploded by moves of player.
+    pub explosions: usize,
+    /// Cells of other players taken by explosions of player.
+    pub cells_captured: usize,
+    /// Longest chain of explosions, by waves and then by cells.
+    pub longest_chain: Chain,
+    /// Most cells owned at the end of a move.
+    pub peak_territory: usize,
+}
+
+/// Move whose explosions are not over yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Turn {
+    player: u8,
+    /// Owners of cells before the move.
+    owners: Vec<Option<u8>>,
+    chain: Chain,
+}
+
+/// Statistics of game for every player.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameStats {
+    players: Vec<PlayerStats>,
+    timeline: Vec<Vec<usize>>,
+    #[serde(skip)]
+    turn: Option<Turn>,
+}
+
+fn owners(board: &Board) -> Vec<Option<u8>> {
+    board
+        .cells()
+        .into_iter()
+        .flatten()
+        .map(|cell| match cell {
+            CellState::NonEmpty(owner, _) => Some(owner),
+            _ => None,
+        })
+        .collect()
+}
+
+impl GameStats {
+    /// Create stats of game for number of players, before any move.
+    pub fn new(players: u8) -> Self {
+        Self {
+            players: vec![PlayerStats::default(); players as usize],
+            timeline: vec![],
+            turn: None,
+        }
+    }
+
+    /// Collect stats by playing all moves and resignations of record.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`RecordError`] if board is invalid or move cannot be played.
+    ///
+    /// [`RecordError`]: crate::record::RecordError
+    pub fn from_record(record: &GameRecord) -> Result<Self, RecordError> {
+        let mut board = record.board()?;
+        let mut stats = Self::new(record.players);
+        for (number, &(player, row, col)) in record.moves.iter().enumerate() {
+            for resigned in record.resigned_after(number) {
+                board
+                    .resign(resigned)
+                    .map_err(|err| RecordError::InvalidResign(number, err))?;
+            }
+            board
+                .player_move(player, row, col)
+                .map_err(|err| RecordError::InvalidMove(number + 1, err))?;
+            stats.moved(player, &board);
+            stats.settle(&mut board);
+        }
+        Ok(stats)
+    }
+
+    /// Provides stats of each player.
+    pub fn players(&self) -> &[PlayerStats] {
+        &self.players
+    }
+
+    /// Provides cells owned by each player after each move.
+    pub fn timeline(&self) -> &[Vec<usize>] {
+        &self.timeline
+    }
+
+    /// Records move of player, call once board accepted the move.
+    pub fn moved(&mut self, player: u8, board: &Board) {
+        self.players[player as usize].atoms_placed += 1;
+        // Only cell of the move changed, which is empty or already of player.
+        self.turn = Some(Turn {
+            player,
+            owners: owners(board),
+            chain: Chain::default(),
+        });
+        self.iterated(board);
+    }
+
+    /// Records board after [`Board::next_iteration`], move ends once board
+    /// waits for next move or game is over.
+    ///
+    /// [`Board::next_iteration`]: crate::board::Board::next_iteration
+    pub fn iterated(&mut self, board: &Board) {
+        let Some(ref mut turn) = self.turn else {
+            return;
+        };
+        match board.state() {
+            BoardState::Explosion(cells) => {
+                turn.chain.waves += 1;
+                turn.chain.cells += cells.len();
+            }
+            BoardState::CheckWinCondition => {}
+            BoardState::Wait | BoardState::GameOver(_) | BoardState::Draw => self.end_turn(board),
+        }
+    }
+
+    /// Runs explosions of board till it settles, recording each step.
+    pub fn settle(&mut self, board: &mut Board) {
+        while board.next_iteration() {
+            self.iterated(board);
+        }
+        self.iterated(board);
+    }
+
+    fn end_turn(&mut self, board: &Board) {
+        let Some(turn) = self.turn.take() else {
+            return;
+        };
+        let owners = owners(board);
+        let player = &mut self.players[turn.player as usize];
+        player.explosions += turn.chain.cells;
+        player.longest_chain = player.longest_chain.max(turn.chain);
+        player.cells_captured += turn
+            .owners
+            .iter()
+            .zip(&owners)
+            .filter(|(before, after)| {
+                matches!(before, Some(owner) if *owner != turn.player)
+                    && **after == Some(turn.player)
+            })
+            .count();
+        let mut territory = vec![0; self.players.len()];
+        for owner in owners.into_iter().flatten() {
+            territory[owner as usize] += 1;
+        }
+        for (player, cells) in self.players.iter_mut().zip(&territory) {
+            player.peak_territory = player.peak_territory.max(*cells);
+        }
+        self.timeline.push(territory);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Rules;
+
+    #[test]
+    fn chain_of_explosions() {
+        let mut record = GameRecord::new(3, 3, 2, Rules::Classic);
+        record.moves = vec![
+            (0, 0, 0),
+            (1, 2, 2),
+            (0, 0, 1),
+            (1, 2, 1),
+            (0, 0, 1),
+            (1, 1, 1),
+            (0, 0, 0),
+        ];
+        let stats = GameStats::from_record(&record).unwrap();
+        let player = &stats.players()[0];
+        assert_eq!(player.atoms_placed, 4);
+        // Corner explodes, then the edge cell next to it, taking the center.
+        assert_eq!(player.longest_chain, Chain { waves: 2, cells: 2 });
+        assert_eq!(player.explosions, 2);
+        assert_eq!(player.cells_captured, 1);
+        assert_eq!(stats.timeline().len(), 7);
+        assert_eq!(stats.players()[1].peak_territory, 3);
+    }
+
+    #[test]
+    fn moves_without_explosions_end_at_once() {
+        let mut board = Board::new(3, 3, 2);
+        let mut stats = GameStats::new(2);
+        board.player_move(0, 1, 1).unwrap();
+        stats.moved(0, &board);
+        assert_eq!(stats.timeline(), &[vec![1, 0]]);
+        stats.settle(&mut board);
+        assert_eq!(stats.timeline().len(), 1);
+        assert_eq!(stats.players()[0].longest_chain, Chain::default());
+    }
+}
@@ -0,0 +1,390 @@
+//! Provides round robin tournament between computer players.
+//!
+//! # Examples
+//!
+//! ```
+//! use chain_reaction::tournament::Tournament;
+//!
+//! let tournament = Tournament {
+//!     ais: vec!["random".to_string(), "greedy".to_string()],
+//!     boards: vec![(3, 3)],
+//!     players: 2,
+//!     rounds: 2,
+//!     seed: 0,
+//!     threads: 2,
+//! };
+//! let report = tournament.run().unwrap();
+//! // 2 seatings with 2 rounds each.
+//! assert_eq!(report.games.len(), 4);
+//! ```
+
+use crate::ai;
+use crate::board::{Board, BoardState};
+use std::cmp;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+const INITIAL_ELO: f64 = 1500.0;
+const ELO_K: f64 = 32.0;
+
+/// Tournament Errors.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TournamentError {
+    /// Computer player with name does not exist.
+    UnknownAi(String),
+    /// Less computer players than players in each game.
+    NotEnoughAis,
+    /// Less than 2 players in each game.
+    TooFewPlayers,
+    /// Board has less than 3 rows or columns.
+    BoardTooSmall,
+}
+
+/// Tournament configuration.
+pub struct Tournament {
+    /// Names of computer players taking part.
+    pub ais: Vec<String>,
+    /// Board sizes as rows and columns.
+    pub boards: Vec<(usize, usize)>,
+    /// Players in each game.
+    pub players: u8,
+    /// Games played for each seating.
+    pub rounds: usize,
+    /// Seed for computer players.
+    pub seed: u64,
+    /// Threads playing games in parallel.
+    pub threads: usize,
+}
+
+/// Result of single game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameResult {
+    /// Board size as rows and columns.
+    pub board: (usize, usize),
+    /// Index of computer player for each seat.
+    pub seats: Vec<usize>,
+    /// Index of winning computer player, `None` if game did not finish.
+    pub winner: Option<usize>,
+    /// Place of each seat from 1, see [`Board::standings`].
+    ///
+    /// [`Board::standings`]: crate::board::Board::standings
+    pub places: Vec<u8>,
+    /// Number of moves.
+    pub moves: usize,
+    /// Explosion waves of each move which exploded.
+    pub chains: Vec<usize>,
+}
+
+/// Statistics of single computer player.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AiStats {
+    /// Name of computer player.
+    pub name: String,
+    /// Games played.
+    pub games: usize,
+    /// Games won.
+    pub wins: usize,
+    /// Elo rating estimate.
+    pub elo: f64,
+}
+
+impl AiStats {
+    /// Provides fraction of games won.
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.games as f64
+        }
+    }
+}
+
+/// Tournament report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    /// Statistics in order of computer players in tournament.
+    pub ais: Vec<AiStats>,
+    /// Results of all games in order they were scheduled.
+    pub games: Vec<GameResult>,
+}
+
+impl Report {
+    /// Provides average moves per game.
+    pub fn average_game_length(&self) -> f64 {
+        average(self.games.iter().map(|game| game.moves))
+    }
+
+    /// Provides average explosion waves of moves which exploded.
+    pub fn average_chain_length(&self) -> f64 {
+        average(
+            self.games
+                .iter()
+                .flat_map(|game| game.chains.iter().copied()),
+        )
+    }
+}
+
+fn average(values: impl Iterator<Item = usize>) -> f64 {
+    let (sum, count) = values.fold((0, 0), |(sum, count), value| (sum + value, count + 1));
+    if count == 0 {
+        0.0
+    } else {
+        sum as f64 / count as f64
+    }
+}
+
+/// All ways of picking `size` items from `0..n` in increasing order.
+fn combinations(n: usize, size: usize) -> Vec<Vec<usize>> {
+    if size == 0 {
+        return vec![vec![]];
+    }
+    (size - 1..n)
+        .flat_map(|last| {
+            combinations(last, size - 1).into_iter().map(move |mut c| {
+                c.push(last);
+                c
+            })
+        })
+        .collect()
+}
+
+impl Tournament {
+    /// Provides games to be played, each game is board size and seating.
+    fn schedule(&self) -> Vec<((usize, usize), Vec<usize>)> {
+        let seats = self.players as usize;
+        let mut games = vec![];
+        for &board in self.boards.iter() {
+            for group in combinations(self.ais.len(), seats) {
+                // Alternate seats so no computer player always moves first.
+                for rotation in 0..seats {
+                    let mut seating = group.clone();
+                    seating.rotate_left(rotation);
+                    for _ in 0..self.rounds {
+                        games.push((board, seating.clone()));
+                    }
+                }
+            }
+        }
+        games
+    }
+
+    fn play(&self, game: usize, board_size: (usize, usize), seats: Vec<usize>) -> GameResult {
+        let (rows, cols) = board_size;
+        let mut board = Board::new(rows, cols, self.players);
+        let seed = self.seed.wrapping_add(game as u64 * seats.len() as u64);
+        let mut ais: Vec<_> = seats
+            .iter()
+            .enumerate()
+            .map(|(seat, &ai)| {
+                ai::from_name(&self.ais[ai], seed.wrapping_add(seat as u64))
+                    .expect("computer players are validated")
+            })
+            .collect();
+        let mut result = GameResult {
+            board: board_size,
+            winner: None,
+            places: vec![],
+            moves: 0,
+            chains: vec![],
+            seats,
+        };
+        // Each move adds an atom, board cannot hold atoms of that many moves.
+        let max_moves = rows * cols * 4;
+        while result.moves < max_moves {
+            if let BoardState::GameOver(winner) = board.state() {
+                result.winner = Some(result.seats[*winner as usize]);
+                break;
+            }
+            let player = board.current_player_id();
+            let (row, col) = match ais[player as usize].choose_move(&board) {
+                Some(mv) => mv,
+                None => break,
+            };
+            if board.player_move(player, row, col).is_err() {
+                break;
+            }
+            result.moves += 1;
+            let waves = board.settle();
+            if waves > 0 {
+                result.chains.push(waves);
+            }
+        }
+        result.places = vec![0; result.seats.len()];
+        for standing in board.standings() {
+            result.places[standing.player as usize] = standing.place;
+        }
+        result
+    }
+
+    /// Play all games of tournament.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`TournamentError`] if configuration is invalid.
+    ///
+    /// [`TournamentError`]: self::TournamentError
+    pub fn run(&self) -> Result<Report, TournamentError> {
+        if let Some(name) = self
+            .ais
+            .iter()
+            .find(|name| !ai::NAMES.contains(&name.as_str()))
+        {
+            return Err(TournamentError::UnknownAi(name.clone()));
+        }
+        if self.players < 2 {
+            return Err(TournamentError::TooFewPlayers);
+        }
+        if self.ais.len() < self.players as usize {
+            return Err(TournamentError::NotEnoughAis);
+        }
+        if self.boards.iter().any(|&(rows, cols)| rows < 3 || cols < 3) {
+            return Err(TournamentError::BoardTooSmall);
+        }
+
+        let schedule = self.schedule();
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(vec![None; schedule.len()]);
+        std::thread::scope(|scope| {
+            for _ in 0..self.threads.max(1) {
+                scope.spawn(|| loop {
+                    let game = next.fetch_add(1, Ordering::Relaxed);
+                    let Some((board, seats)) = schedule.get(game) else {
+                        break;
+                    };
+                    let result = self.play(game, *board, seats.clone());
+                    results.lock().unwrap()[game] = Some(result);
+                });
+            }
+        });
+        let games: Vec<GameResult> = results
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .map(|result| result.expect("all games are played"))
+            .collect();
+
+        let mut ais: Vec<_> = self
+            .ais
+            .iter()
+            .map(|name| AiStats {
+                name: name.clone(),
+                games: 0,
+                wins: 0,
+                elo: INITIAL_ELO,
+            })
+            .collect();
+        for game in games.iter() {
+            for &ai in game.seats.iter() {
+                ais[ai].games += 1;
+            }
+            if let Some(winner) = game.winner {
+                ais[winner].wins += 1;
+            }
+            update_elo(&mut ais, game);
+        }
+        Ok(Report { ais, games })
+    }
+}
+
+/// Update ratings treating game as pairwise matches between seats, won by
+/// seat with better place.
+fn update_elo(ais: &mut [AiStats], game: &GameResult) {
+    let k = ELO_K / (game.seats.len() - 1) as f64;
+    let mut changes = vec![0.0; ais.len()];
+    for (i, &a) in game.seats.iter().enumerate() {
+        for (j, &b) in game.seats.iter().enumerate().skip(i + 1) {
+            let expected = 1.0 / (1.0 + 10f64.powf((ais[b].elo - ais[a].elo) / 400.0));
+            let actual = match game.places[i].cmp(&game.places[j]) {
+                cmp::Ordering::Less => 1.0,
+                cmp::Ordering::Greater => 0.0,
+                cmp::Ordering::Equal => 0.5,
+            };
+            changes[a] += k * (actual - expected);
+            changes[b] -= k * (actual - expected);
+        }
+    }
+    for (ai, change) in ais.iter_mut().zip(changes) {
+        ai.elo += change;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tournament(ais: &[&str], players: u8, threads: usize) -> Tournament {
+        Tournament {
+            ais: ais.iter().map(|s| s.to_string()).collect(),
+            boards: vec![(3, 3), (4, 5)],
+            players,
+            rounds: 2,
+            seed: 3,
+            threads,
+        }
+    }
+
+    #[test]
+    fn combinations_are_unique() {
+        assert_eq!(
+            combinations(4, 2),
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![1, 2],
+                vec![0, 3],
+                vec![1, 3],
+                vec![2, 3]
+            ]
+        );
+        assert_eq!(combinations(2, 3), Vec::<Vec<usize>>::new());
+    }
+
+    #[test]
+    fn seats_alternate() {
+        let report = tournament(&["random", "greedy", "minimax"], 3, 1)
+            .run()
+            .unwrap();
+        // 2 boards, 1 group, 3 rotations, 2 rounds.
+        assert_eq!(report.games.len(), 12);
+        for ai in report.ais.iter() {
+            assert_eq!(ai.games, 12);
+        }
+        for seat in 0..3 {
+            let first = report.games.iter().filter(|g| g.seats[0] == seat).count();
+            assert_eq!(first, 4);
+        }
+    }
+
+    #[test]
+    fn threads_do_not_change_results() {
+        let ais = ["random", "greedy", "random"];
+        assert_eq!(
+            tournament(&ais, 2, 1).run().unwrap(),
+            tournament(&ais, 2, 4).run().unwrap()
+        );
+    }
+
+    #[test]
+    fn elo_is_zero_sum() {
+        let report = tournament(&["random", "greedy"], 2, 2).run().unwrap();
+        let total: f64 = report.ais.iter().map(|ai| ai.elo).sum();
+        assert!((total - 2.0 * INITIAL_ELO).abs() < 1e-6);
+        assert!(report.average_game_length() > 0.0);
+    }
+
+    #[test]
+    fn invalid_tournaments() {
+        assert_eq!(
+            tournament(&["random", "perfect"], 2, 1).run(),
+            Err(TournamentError::UnknownAi("perfect".to_string()))
+        );
+        assert_eq!(
+            tournament(&["random"], 2, 1).run(),
+            Err(TournamentError::NotEnoughAis)
+        );
+        assert_eq!(
+            tournament(&["random", "random"], 1, 1).run(),
+            Err(TournamentError::TooFewPlayers)
+        );
+    }
+}
@@ -0,0 +1,240 @@
+//! Provides server side of WebSocket, enough for text messages of browsers.
+//!
+//! Only what browsers need is supported: handshake, text messages, which
+//! may be fragmented, and close. Pings are read but not answered, browsers
+//! do not send them on their own.
+
+use std::io::{self, BufRead, ErrorKind, Read, Write};
+
+/// Largest message accepted from client.
+pub(crate) const MAX_MESSAGE: usize = 64 * 1024;
+
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn invalid(reason: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, reason.to_string())
+}
+
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut hash: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&(data.len() as u64 * 8).to_be_bytes());
+    for chunk in message.chunks(64) {
+        let mut words = [0u32; 80];
+        for (word, bytes) in words.iter_mut().zip(chunk.chunks(4)) {
+            *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        for i in 16..80 {
+            words[i] = (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = hash;
+        for (i, word) in words.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            (e, d, c, b, a) = (d, c, b.rotate_left(30), a, temp);
+        }
+        for (value, add) in hash.iter_mut().zip([a, b, c, d, e]) {
+            *value = value.wrapping_add(add);
+        }
+    }
+    let mut out = [0; 20];
+    for (bytes, value) in out.chunks_mut(4).zip(hash) {
+        bytes.copy_from_slice(&value.to_be_bytes());
+    }
+    out
+}
+
+fn base64(data: &[u8]) -> String {
+    let mut out = String::new();
+    for chunk in data.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, byte)| {
+            bits | (*byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(bits >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Provides answer to `Sec-WebSocket-Key` of client.
+fn accept_key(key: &str) -> String {
+    base64(&sha1(format!("{}{}", key.trim(), GUID).as_bytes()))
+}
+
+/// Reads HTTP upgrade request of client and accepts it.
+pub(crate) fn accept(reader: &mut impl BufRead, writer: &mut impl Write) -> io::Result<()> {
+    let mut key = None;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+        if line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("sec-websocket-key") {
+                key = Some(value.trim().to_string());
+            }
+        }
+    }
+    let Some(key) = key else {
+        writer.write_all(b"HTTP/1.1 400 Bad Request\r\n\r\n")?;
+        return Err(invalid("not a websocket request"));
+    };
+    write!(
+        writer,
+        "HTTP/1.1 101 Switching Protocols\r\n\
+         Upgrade: websocket\r\n\
+         Connection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(&key)
+    )
+}
+
+/// Reads text message of client, `None` once client closes connection.
+pub(crate) fn read_message(reader: &mut impl Read) -> io::Result<Option<String>> {
+    let mut message = vec![];
+    loop {
+        let mut head = [0; 2];
+        reader.read_exact(&mut head)?;
+        let fin = head[0] & 0x80 != 0;
+        let opcode = head[0] & 0x0f;
+        let len = match head[1] & 0x7f {
+            126 => {
+                let mut len = [0; 2];
+                reader.read_exact(&mut len)?;
+                u16::from_be_bytes(len) as u64
+            }
+            127 => {
+                let mut len = [0; 8];
+                reader.read_exact(&mut len)?;
+                u64::from_be_bytes(len)
+            }
+            len => len as u64,
+        };
+        if message.len() as u64 + len > MAX_MESSAGE as u64 {
+            return Err(invalid("message too long"));
+        }
+        let mut mask = [0; 4];
+        if head[1] & 0x80 != 0 {
+            reader.read_exact(&mut mask)?;
+        }
+        let mut payload = vec![0; len as usize];
+        reader.read_exact(&mut payload)?;
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+        match opcode {
+            0x0 | 0x1 => message.extend(payload),
+            0x8 => return Ok(None),
+            0x9 | 0xa => continue,
+            _ => return Err(invalid("only text messages are supported")),
+        }
+        if fin {
+            return String::from_utf8(message)
+                .map(Some)
+                .map_err(|_| invalid("message is not utf-8"));
+        }
+    }
+}
+
+/// Writes text message to client.
+pub(crate) fn write_message(writer: &mut impl Write, text: &str) -> io::Result<()> {
+    let mut frame = vec![0x81];
+    match text.len() {
+        len @ 0..=125 => frame.push(len as u8),
+        len @ 126..=0xffff => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(text.as_bytes());
+    writer.write_all(&frame)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Provides frame as sent by browsers, masked.
+    fn client_frame(opcode: u8, fin: bool, payload: &[u8]) -> Vec<u8> {
+        let mask = [1, 2, 3, 4];
+        let mut frame = vec![
+            opcode | if fin { 0x80 } else { 0 },
+            0x80 | payload.len() as u8,
+        ];
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        frame
+    }
+
+    #[test]
+    fn handshake() {
+        assert_eq!(base64(b"ab"), "YWI=");
+        assert_eq!(
+            base64(&sha1(b"abc")),
+            base64(&[
+                0xa9, 0x99, 0x3e, 0x36, 0x47, 0x06, 0x81, 0x6a, 0xba, 0x3e, 0x25, 0x71, 0x78, 0x50,
+                0xc2, 0x6c, 0x9c, 0xd0, 0xd8, 0x9d
+            ])
+        );
+        // Example of RFC 6455.
+        let request = "GET /chat HTTP/1.1\r\nHost: server.example.com\r\n\
+                       Upgrade: websocket\r\nConnection: Upgrade\r\n\
+                       Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n";
+        let mut response = vec![];
+        accept(&mut request.as_bytes(), &mut response).unwrap();
+        let response = String::from_utf8(response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 101"));
+        assert!(response.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n"));
+        let mut response = vec![];
+        assert!(accept(&mut "GET / HTTP/1.1\r\n\r\n".as_bytes(), &mut response).is_err());
+        assert!(response.starts_with(b"HTTP/1.1 400"));
+    }
+
+    #[test]
+    fn messages() {
+        let mut frames = client_frame(0x1, false, b"hel");
+        frames.extend(client_frame(0x9, true, b""));
+        frames.extend(client_frame(0x0, true, b"lo"));
+        frames.extend(client_frame(0x8, true, b""));
+        let mut frames = &frames[..];
+        assert_eq!(read_message(&mut frames).unwrap().as_deref(), Some("hello"));
+        assert_eq!(read_message(&mut frames).unwrap(), None);
+        assert!(read_message(&mut &client_frame(0x2, true, b"x")[..]).is_err());
+
+        let mut frame = vec![];
+        write_message(&mut frame, "hi").unwrap();
+        assert_eq!(frame, b"\x81\x02hi");
+        let mut frame = vec![];
+        write_message(&mut frame, &"x".repeat(300)).unwrap();
+        assert_eq!(frame[..4], [0x81, 126, 1, 44]);
+    }
+}
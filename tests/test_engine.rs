@@ -0,0 +1,77 @@
+use chain_reaction::board::{Board, BoardState, Rules};
+use chain_reaction::engine::{EngineError, Host};
+use chain_reaction::record::Turn;
+use std::time::Duration;
+
+const TIME: Duration = Duration::from_millis(100);
+
+fn engine(ai: &str, seed: u64) -> Host {
+    let command = format!(
+        "{} engine --ai {} --seed {}",
+        env!("CARGO_BIN_EXE_cli"),
+        ai,
+        seed
+    );
+    Host::spawn(&command).unwrap()
+}
+
+#[test]
+fn engines_play_each_other() {
+    let mut hosts = [engine("random", 1), engine("greedy", 2)];
+    for host in hosts.iter_mut() {
+        host.new_game(4, 4, 2, Rules::Wrap).unwrap();
+    }
+    let mut board = Board::with_rules(4, 4, 2, Rules::Wrap);
+    let mut moves = vec![];
+    while board.state() == &BoardState::Wait {
+        assert!(moves.len() < 200, "game too long");
+        let player = board.current_player_id();
+        let (row, col) = hosts[player as usize].best_move(&moves, TIME).unwrap();
+        board.player_move(player, row, col).unwrap();
+        board.settle();
+        moves.push(Turn::Move(row, col));
+    }
+    assert!(matches!(board.state(), BoardState::GameOver(_)));
+}
+
+#[test]
+fn engine_errors() {
+    let mut host = engine("random", 0);
+    assert!(matches!(
+        host.best_move(&[], TIME),
+        Err(EngineError::Engine(_))
+    ));
+    host.new_game(3, 3, 2, Rules::Classic).unwrap();
+    assert!(matches!(
+        host.best_move(&[Turn::Move(0, 0), Turn::Move(0, 0)], TIME),
+        Err(EngineError::Engine(_))
+    ));
+    assert!(Host::spawn("chain-reaction-no-such-engine").is_err());
+}
+
+#[test]
+fn late_moves_are_dropped() {
+    // Bot answers its first position only once test lets it, after timeout.
+    let name = format!("chain-reaction-slow-engine-{}", std::process::id());
+    let script = std::env::temp_dir().join(format!("{}.sh", name));
+    let answer = std::env::temp_dir().join(format!("{}.go", name));
+    std::fs::write(
+        &script,
+        "n=0\nwhile read line; do case \"$line\" in\n\
+         isready) echo readyok;;\n\
+         go*) n=$((n+1)); if [ $n = 1 ]; then \
+         while [ ! -e \"$1\" ]; do sleep 0.01; done; echo bestmove 0 0; \
+         else echo bestmove 1 1; fi;;\n\
+         quit) exit;;\nesac; done\n",
+    )
+    .unwrap();
+    let command = format!("sh {} {}", script.display(), answer.display());
+    let mut host = Host::spawn(&command).unwrap();
+    host.new_game(3, 3, 2, Rules::Classic).unwrap();
+    assert_eq!(host.best_move(&[], TIME), Err(EngineError::Timeout));
+    std::fs::write(&answer, "").unwrap();
+    assert_eq!(host.best_move(&[], TIME), Ok((1, 1)));
+    drop(host);
+    let _ = std::fs::remove_file(script);
+    let _ = std::fs::remove_file(answer);
+}
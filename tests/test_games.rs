@@ -1,4 +1,4 @@
-use chain_reaction::board::{Board, BoardState};
+use chain_reaction::board::{Board, BoardState, Rules};
 
 /// player, x, y, iteration
 #[derive(Debug)]
@@ -12,10 +12,11 @@ struct Game {
     rows: usize,
     cols: usize,
     players: u8,
+    rules: Rules,
 }
 
 fn assert_game(game: Game) {
-    let mut board = Board::new(game.rows, game.cols, game.players);
+    let mut board = Board::with_rules(game.rows, game.cols, game.players, game.rules);
     for Move(player, row, col, iter) in game.moves {
         let result = board.player_move(player, row, col);
         assert!(result.is_ok(), "{:?}", result);
@@ -38,6 +39,29 @@ fn short_game() {
         rows: 5,
         cols: 5,
         players: 2,
+        rules: Rules::Classic,
+    };
+    assert_game(game);
+}
+
+#[test]
+fn wrapped_corner_explodes_across_edges() {
+    let game = Game {
+        winner: None,
+        moves: vec![
+            Move(0, 0, 0, 1),
+            Move(1, 1, 1, 1),
+            Move(0, 0, 0, 1),
+            Move(1, 1, 1, 1),
+            Move(0, 0, 0, 1),
+            Move(1, 1, 1, 1),
+            Move(0, 0, 0, 2),
+            Move(1, 1, 1, 2),
+        ],
+        rows: 3,
+        cols: 3,
+        players: 2,
+        rules: Rules::Wrap,
     };
     assert_game(game);
 }
@@ -0,0 +1,9 @@
+use chain_reaction::puzzle::parse;
+
+#[test]
+fn every_puzzle_has_solution() {
+    let puzzles = parse(include_str!("../puzzles/basic.txt")).unwrap();
+    for puzzle in puzzles {
+        assert!(!puzzle.solutions().is_empty(), "{}", puzzle.name());
+    }
+}
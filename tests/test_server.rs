@@ -0,0 +1,232 @@
+use chain_reaction::board::Rules;
+use chain_reaction::net::{self, ClientMessage, Rooms, ServerMessage, Transport};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+
+struct Client {
+    stream: TcpStream,
+    lines: BufReader<TcpStream>,
+}
+
+impl Client {
+    fn connect(address: SocketAddr) -> Self {
+        let stream = TcpStream::connect(address).unwrap();
+        let lines = BufReader::new(stream.try_clone().unwrap());
+        Self { stream, lines }
+    }
+
+    fn send(&mut self, message: ClientMessage) {
+        let line = serde_json::to_string(&message).unwrap();
+        writeln!(self.stream, "{}", line).unwrap();
+    }
+
+    fn receive(&mut self) -> ServerMessage {
+        let mut line = String::new();
+        self.lines.read_line(&mut line).unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    /// Receives messages till settled board.
+    fn receive_state(&mut self) -> Vec<ServerMessage> {
+        let mut messages = vec![];
+        loop {
+            let message = self.receive();
+            let state = matches!(message, ServerMessage::State(_));
+            messages.push(message);
+            if state {
+                return messages;
+            }
+        }
+    }
+}
+
+fn server(players: u8) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let rooms = Rooms::new(3, 3, players, Rules::Classic);
+    std::thread::spawn(move || net::serve(vec![(listener, Transport::Lines)], rooms));
+    address
+}
+
+/// Client speaking WebSocket like browsers, with short unfragmented messages.
+struct Browser {
+    stream: TcpStream,
+}
+
+impl Browser {
+    fn connect(address: SocketAddr) -> Self {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "GET / HTTP/1.1\r\nHost: {}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+             Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n",
+            address
+        )
+        .unwrap();
+        let mut response = vec![];
+        while !response.ends_with(b"\r\n\r\n") {
+            let mut byte = [0];
+            stream.read_exact(&mut byte).unwrap();
+            response.push(byte[0]);
+        }
+        assert!(response.starts_with(b"HTTP/1.1 101"));
+        Self { stream }
+    }
+
+    fn send(&mut self, message: ClientMessage) {
+        let text = serde_json::to_string(&message).unwrap();
+        let mask = [7, 1, 3, 9];
+        let mut frame = vec![0x81, 0x80 | text.len() as u8];
+        frame.extend_from_slice(&mask);
+        frame.extend(text.bytes().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        self.stream.write_all(&frame).unwrap();
+    }
+
+    fn receive(&mut self) -> ServerMessage {
+        let mut head = [0; 2];
+        self.stream.read_exact(&mut head).unwrap();
+        assert_eq!(head[0], 0x81);
+        let len = match head[1] {
+            126 => {
+                let mut len = [0; 2];
+                self.stream.read_exact(&mut len).unwrap();
+                u16::from_be_bytes(len) as usize
+            }
+            len => len as usize,
+        };
+        let mut text = vec![0; len];
+        self.stream.read_exact(&mut text).unwrap();
+        serde_json::from_slice(&text).unwrap()
+    }
+}
+
+fn join(name: &str) -> ClientMessage {
+    ClientMessage::Join {
+        name: name.to_string(),
+        room: None,
+    }
+}
+
+#[test]
+fn loopback_game() {
+    let address = server(2);
+    let mut alice = Client::connect(address);
+    let mut bob = Client::connect(address);
+    alice.send(join("alice"));
+    assert!(matches!(alice.receive(), ServerMessage::Lobby { .. }));
+    bob.send(join("bob"));
+    assert!(matches!(bob.receive(), ServerMessage::Lobby { .. }));
+    match alice.receive() {
+        ServerMessage::Lobby { names, players } => {
+            assert_eq!((names.len(), players), (2, 2))
+        }
+        message => panic!("unexpected {:?}", message),
+    }
+    assert!(matches!(
+        alice.receive(),
+        ServerMessage::Welcome { player: 0, .. }
+    ));
+    assert!(matches!(
+        bob.receive(),
+        ServerMessage::Welcome { player: 1, .. }
+    ));
+    alice.receive_state();
+    bob.receive_state();
+
+    writeln!(bob.stream, "not json").unwrap();
+    assert!(matches!(bob.receive(), ServerMessage::Error { .. }));
+    bob.send(ClientMessage::Move { row: 1, col: 1 });
+    assert!(matches!(bob.receive(), ServerMessage::Error { .. }));
+
+    let mut messages = vec![];
+    for (client, row, col) in [(0, 0, 0), (1, 1, 0), (0, 0, 0)] {
+        let player = if client == 0 { &mut alice } else { &mut bob };
+        player.send(ClientMessage::Move { row, col });
+        alice.receive_state();
+        messages = bob.receive_state();
+    }
+    assert_eq!(
+        messages[0],
+        ServerMessage::Moved {
+            player: 0,
+            row: 0,
+            col: 0
+        }
+    );
+    match messages[1] {
+        ServerMessage::Wave {
+            wave,
+            ref exploding,
+            ..
+        } => assert_eq!((wave, &exploding[..]), (1, &[(0, 0)][..])),
+        ref message => panic!("unexpected {:?}", message),
+    }
+    match messages[2] {
+        ServerMessage::State(ref state) => {
+            assert_eq!(state.winner, Some(0));
+            assert_eq!(state.names, vec!["alice", "bob"]);
+            assert_eq!(state.standings[1].player, 1);
+        }
+        ref message => panic!("unexpected {:?}", message),
+    }
+}
+
+#[test]
+fn disconnected_player_is_reported() {
+    let address = server(2);
+    let mut alice = Client::connect(address);
+    let mut bob = Client::connect(address);
+    alice.send(join("alice"));
+    alice.receive();
+    bob.send(join("bob"));
+    alice.receive_state();
+    drop(bob);
+    assert_eq!(alice.receive(), ServerMessage::Left { player: 1 });
+}
+
+#[test]
+fn browser_creates_room() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let websocket = TcpListener::bind("127.0.0.1:0").unwrap();
+    let (address, browser_address) = (
+        listener.local_addr().unwrap(),
+        websocket.local_addr().unwrap(),
+    );
+    let rooms = Rooms::new(3, 3, 2, Rules::Classic);
+    let listeners = vec![
+        (listener, Transport::Lines),
+        (websocket, Transport::WebSocket),
+    ];
+    std::thread::spawn(move || net::serve(listeners, rooms));
+
+    let mut browser = Browser::connect(browser_address);
+    browser.send(ClientMessage::Create {
+        name: "alice".to_string(),
+        rows: 4,
+        cols: 4,
+        players: 2,
+        rules: Rules::Wrap,
+    });
+    let ServerMessage::Room { code } = browser.receive() else {
+        panic!("room expected");
+    };
+    assert!(matches!(browser.receive(), ServerMessage::Lobby { .. }));
+    let mut bob = Client::connect(address);
+    bob.send(ClientMessage::Join {
+        name: "bob".to_string(),
+        room: Some(code),
+    });
+    assert!(matches!(bob.receive(), ServerMessage::Lobby { .. }));
+    assert!(matches!(browser.receive(), ServerMessage::Lobby { .. }));
+    assert!(matches!(
+        browser.receive(),
+        ServerMessage::Welcome { player: 0, .. }
+    ));
+    match browser.receive() {
+        ServerMessage::State(state) => assert_eq!((state.rows, state.rules), (4, Rules::Wrap)),
+        message => panic!("unexpected {:?}", message),
+    }
+    browser.send(ClientMessage::Move { row: 0, col: 0 });
+    assert!(matches!(browser.receive(), ServerMessage::Moved { .. }));
+    bob.receive_state();
+}